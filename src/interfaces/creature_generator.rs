@@ -0,0 +1,5 @@
+use crate::models::{character::Character, creature::Creature};
+
+pub trait CreatureGenerator {
+    fn generate_creature(&self, creature: &Creature) -> Character;
+}
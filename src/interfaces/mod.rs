@@ -1,4 +1,6 @@
 pub mod character_generator;
+pub mod creature_generator;
 pub mod random_provider;
 pub mod resource_bag;
 pub mod character_displayer;
+pub mod npc_store;
@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+use crate::models::saved_npc::SavedNpc;
+use crate::types::command::CommandError;
+
+pub trait NpcStore {
+    // Where the NPC was written to
+    fn save(&self, npc: &SavedNpc) -> Result<PathBuf, CommandError>;
+    fn load(&self, id: &str) -> Result<SavedNpc, CommandError>;
+    // Ordered by id
+    fn list(&self) -> Result<Vec<SavedNpc>, CommandError>;
+    fn delete(&self, id: &str) -> Result<(), CommandError>;
+}
@@ -2,4 +2,20 @@ use crate::models::generation_base::GenerationBase;
 
 pub trait RandomProvider {
     fn generate(&self, base: &GenerationBase) -> u32;
+
+    // Independent provider for one of many generations, forking the same stream twice gives the same rolls
+    fn fork(&self, stream: u64) -> Self where Self: Sized;
+
+    // Seed reproducing the rolls of this provider, None when they cannot be reproduced
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+
+    // Uniformly picks an index in 0..len, None for an empty range
+    fn pick_index(&self, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        Some(self.generate(&GenerationBase{base_value: 0, dices: vec![len as u32]}) as usize - 1)
+    }
 }
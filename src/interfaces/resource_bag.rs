@@ -1,3 +1,3 @@
 pub trait ResourceBag<T> {
-    fn get_by_id(&self, id: String) -> T;
+    fn get_by_id(&self, id: &str) -> Option<T>;
 }
@@ -0,0 +1,50 @@
+use std::slice::Iter;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum HitLocation {
+    Head,
+    Arms,
+    Body,
+    Legs,
+}
+
+impl HitLocation {
+    pub fn iterator() -> Iter<'static, HitLocation> {
+        const LOCATIONS: [HitLocation; 4] = [HitLocation::Head, HitLocation::Arms, HitLocation::Body, HitLocation::Legs];
+        LOCATIONS.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            HitLocation::Head => "Head",
+            HitLocation::Arms => "Arms",
+            HitLocation::Body => "Body",
+            HitLocation::Legs => "Legs",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Armour {
+    pub name: String,
+    pub locations: Vec<HitLocation>,
+    pub armour_points: u32,
+    #[serde(default)]
+    pub qualities: Vec<String>,
+    #[serde(default)]
+    pub flaws: Vec<String>,
+    #[serde(default)]
+    pub encumbrance: u32,
+}
+
+impl Armour {
+    pub fn formatted_locations(&self) -> String {
+        self.locations.iter().map(|location| location.to_str()).collect::<Vec<&str>>().join(", ")
+    }
+
+    pub fn traits(&self) -> Vec<&str> {
+        self.qualities.iter().chain(self.flaws.iter()).map(String::as_str).collect()
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum BaseAttribute {
      WeaponSkill = 1,
      BallisticSkill = 2,
@@ -13,3 +15,10 @@ pub enum BaseAttribute {
      Wounds = 11,
      Movement = 12,
 }
+
+impl BaseAttribute {
+    // Wounds and Movement are derived values, not rolled characteristics
+    pub fn is_characteristic(&self) -> bool {
+        !matches!(self, BaseAttribute::Wounds | BaseAttribute::Movement)
+    }
+}
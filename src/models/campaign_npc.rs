@@ -0,0 +1,50 @@
+use super::saved_npc::SavedNpc;
+
+// How an NPC stands towards another one of the campaign, e.g. "rival" or "sworn enemy"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relationship {
+    pub other: String,
+    pub relationship: String,
+}
+
+// Saved NPC along with what the campaign knows about it
+#[derive(Debug, Clone)]
+pub struct CampaignNpc {
+    pub npc: SavedNpc,
+    pub location: Option<String>,
+    pub faction: Option<String>,
+    pub notes: Option<String>,
+    pub relationships: Vec<Relationship>,
+}
+
+impl CampaignNpc {
+    // One line for each thing known, nothing when the NPC was never annotated
+    pub fn formatted_campaign_details(&self) -> String {
+        let relationships = self.relationships.iter()
+                                              .map(|relationship| format!("{} of {}", relationship.relationship, relationship.other))
+                                              .collect::<Vec<String>>()
+                                              .join(", ");
+        [("Tags", Some(self.npc.tags.join(", "))), ("Location", self.location.clone()), ("Faction", self.faction.clone()),
+         ("Relationships", Some(relationships)), ("Notes", self.notes.clone())]
+            .into_iter()
+            .filter_map(|(label, value)| value.filter(|value| !value.is_empty()).map(|value| format!("{}: {}\n", label, value)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::character::Character;
+
+    #[test]
+    fn details_skip_what_is_unknown() {
+        let npc = SavedNpc { id: "ilsa".into(), tags: vec!["altdorf".into()], seed: None, careers: vec![], species: "human".into(), options: vec![],
+                             character: Character::default() };
+        let mut campaign_npc = CampaignNpc { npc, location: None, faction: Some("Order of the Silver Hammer".into()), notes: None, relationships: vec![] };
+        assert_eq!(campaign_npc.formatted_campaign_details(), "Tags: altdorf\nFaction: Order of the Silver Hammer\n");
+        campaign_npc.relationships = vec![Relationship { other: "gunther".into(), relationship: "sworn enemy".into() }];
+        campaign_npc.npc.tags.clear();
+        assert_eq!(campaign_npc.formatted_campaign_details(), "Faction: Order of the Silver Hammer\nRelationships: sworn enemy of gunther\n");
+    }
+}
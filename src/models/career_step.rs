@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+// Experience spent to enter a new career, doubled when the level being left was not completed
+const CAREER_SWITCH_COST: u32 = 100;
+const INCOMPLETE_SWITCH_COST: u32 = 200;
+pub const CAREER_SEPARATOR: &str = " → ";
+
+// Highest level reached in one career before moving on to the next
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CareerStep {
+    pub career: String,
+    pub level: u8,
+    pub title: String,
+    pub completed: bool,
+}
+
+impl CareerStep {
+    // Cost of leaving this career for the next one
+    pub fn switch_cost(&self) -> u32 {
+        if self.completed { CAREER_SWITCH_COST } else { INCOMPLETE_SWITCH_COST }
+    }
+}
+
+impl std::fmt::Display for CareerStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.title == self.career {
+            write!(f, "{} {}", self.career, self.level)
+        }
+        else {
+            write!(f, "{} {} ({})", self.career, self.level, self.title)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_career_step() {
+        let sergeant = CareerStep { career: "Soldier".into(), level: 3, title: "Sergeant".into(), completed: true };
+        assert_eq!(sergeant.to_string(), "Soldier 3 (Sergeant)");
+        assert_eq!(CareerStep { level: 2, title: "Soldier".into(), ..sergeant.clone() }.to_string(), "Soldier 2");
+        assert_eq!(sergeant.switch_cost(), 100);
+        assert_eq!(CareerStep { completed: false, ..sergeant }.switch_cost(), 200);
+    }
+}
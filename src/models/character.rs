@@ -1,22 +1,243 @@
-use super::{talent::Talent, skill::Skill, trapping::Trapping};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use super::{armour::{Armour, HitLocation}, base_attribute::BaseAttribute, career_step::{CareerStep, CAREER_SEPARATOR}, creature_trait::CreatureTrait, weapon::Weapon, details::Details, personality::Personality, size::Size, spell::Lore, stat_value::StatValue, status::Status, talent::Talent, skill::Skill, trapping::Trapping};
+
+const ARMOUR_TRAIT: &str = "Armour";
+
+// Fields missing from a saved NPC are left at their defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Character {
+    pub name: String,
+    // name of the species, empty for creatures
+    pub species: String,
     pub talents: Vec<Talent>,
     pub skills: Vec<Skill>,
     pub trappings: Vec<Trapping>,
 
     // attributes
-    pub weapon_skill: u32,
-    pub ballistic_skill: u32,
-    pub strength: u32,
-    pub toughness: u32,
-    pub initiative: u32,
-    pub agility: u32,
-    pub dexterity: u32,
-    pub intelligence: u32,
-    pub willpower: u32,
-    pub fellowship: u32,
+    pub weapon_skill: StatValue,
+    pub ballistic_skill: StatValue,
+    pub strength: StatValue,
+    pub toughness: StatValue,
+    pub initiative: StatValue,
+    pub agility: StatValue,
+    pub dexterity: StatValue,
+    pub intelligence: StatValue,
+    pub willpower: StatValue,
+    pub fellowship: StatValue,
     pub wounds: u32,
     pub movement: u32,
+    pub size: Size,
+
+    pub fate: u32,
+    pub fortune: u32,
+    pub resilience: u32,
+    pub resolve: u32,
+    pub corruption: u32,
+
+    pub status: Option<Status>,
+    // careers in the order they were taken
+    pub career_history: Vec<CareerStep>,
+    // group the NPC belongs to, e.g. the gang of an encounter
+    pub affiliation: Option<String>,
+    pub details: Option<Details>,
+    pub personality: Option<Personality>,
+    // lores with the spells, Blessings or Miracles known from them
+    pub spells: Vec<Lore>,
+    // bestiary traits, only creatures have any
+    pub traits: Vec<CreatureTrait>,
+}
+
+impl Character {
+    pub fn characteristic(&self, attribute: BaseAttribute) -> Option<&StatValue> {
+        match attribute {
+            BaseAttribute::WeaponSkill => Some(&self.weapon_skill),
+            BaseAttribute::BallisticSkill => Some(&self.ballistic_skill),
+            BaseAttribute::Strength => Some(&self.strength),
+            BaseAttribute::Toughness => Some(&self.toughness),
+            BaseAttribute::Initiative => Some(&self.initiative),
+            BaseAttribute::Agility => Some(&self.agility),
+            BaseAttribute::Dexterity => Some(&self.dexterity),
+            BaseAttribute::Intelligence => Some(&self.intelligence),
+            BaseAttribute::Willpower => Some(&self.willpower),
+            BaseAttribute::Fellowship => Some(&self.fellowship),
+            BaseAttribute::Wounds | BaseAttribute::Movement => None,
+        }
+    }
+
+    pub fn characteristic_mut(&mut self, attribute: BaseAttribute) -> Option<&mut StatValue> {
+        match attribute {
+            BaseAttribute::WeaponSkill => Some(&mut self.weapon_skill),
+            BaseAttribute::BallisticSkill => Some(&mut self.ballistic_skill),
+            BaseAttribute::Strength => Some(&mut self.strength),
+            BaseAttribute::Toughness => Some(&mut self.toughness),
+            BaseAttribute::Initiative => Some(&mut self.initiative),
+            BaseAttribute::Agility => Some(&mut self.agility),
+            BaseAttribute::Dexterity => Some(&mut self.dexterity),
+            BaseAttribute::Intelligence => Some(&mut self.intelligence),
+            BaseAttribute::Willpower => Some(&mut self.willpower),
+            BaseAttribute::Fellowship => Some(&mut self.fellowship),
+            BaseAttribute::Wounds | BaseAttribute::Movement => None,
+        }
+    }
+
+    // Initial value of a skill is the current value of its characteristic
+    pub fn skill_value(&self, skill: &Skill) -> StatValue {
+        StatValue {
+            initial: self.characteristic(skill.base_attribute).map_or(0, StatValue::current),
+            advances: skill.advances,
+        }
+    }
+
+    pub fn add_characteristic_advances(&mut self, attribute: BaseAttribute, advances: u32) -> bool {
+        match self.characteristic_mut(attribute) {
+            Some(characteristic) => {
+                characteristic.advances += advances;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn talent_level(&self, name: &str) -> u32 {
+        self.talents.iter().find(|talent| talent.name == name).map_or(0, |talent| talent.level)
+    }
+
+    pub fn weapons(&self) -> impl Iterator<Item = &Weapon> {
+        self.trappings.iter().filter_map(|trapping| trapping.weapon.as_ref())
+    }
+
+    pub fn armour(&self) -> impl Iterator<Item = &Armour> {
+        self.trappings.iter().filter_map(|trapping| trapping.armour.as_ref())
+    }
+
+    // Actual damage of a weapon in this character's hands
+    pub fn weapon_damage(&self, weapon: &Weapon) -> u32 {
+        weapon.damage.against(self.strength.bonus())
+    }
+
+    // Armour worn over the same location stacks, natural armour covers every location
+    pub fn armour_points(&self, location: HitLocation) -> u32 {
+        self.armour().filter(|armour| armour.locations.contains(&location)).map(|armour| armour.armour_points).sum::<u32>()
+            + self.traits.iter().filter(|creature_trait| creature_trait.name == ARMOUR_TRAIT).filter_map(|creature_trait| creature_trait.rating).sum::<u32>()
+    }
+
+    // Every career left behind cost experience to switch from
+    pub fn career_switch_cost(&self) -> u32 {
+        self.career_history.iter().rev().skip(1).map(CareerStep::switch_cost).sum()
+    }
+
+    pub fn formatted_career_history(&self) -> String {
+        self.career_history.iter().map(CareerStep::to_string).collect::<Vec<String>>().join(CAREER_SEPARATOR)
+    }
+
+    pub fn is_armoured(&self) -> bool {
+        HitLocation::iterator().any(|location| self.armour_points(*location) > 0)
+    }
+
+    pub fn is_creature(&self) -> bool {
+        !self.traits.is_empty()
+    }
+
+    pub fn formatted_trait(&self, creature_trait: &CreatureTrait) -> String {
+        creature_trait.formatted(self.strength.bonus())
+    }
+
+    pub fn total_encumbrance(&self) -> u32 {
+        self.trappings.iter().map(Trapping::total_encumbrance).sum()
+    }
+
+    pub fn encumbrance_limit(&self) -> u32 {
+        self.strength.bonus() + self.toughness.bonus()
+    }
+
+    pub fn is_overloaded(&self) -> bool {
+        self.total_encumbrance() > self.encumbrance_limit()
+    }
+
+    pub fn add_skill_advances(&mut self, name: &str, advances: u32) -> bool {
+        match self.skills.iter_mut().find(|skill| skill.name == name) {
+            Some(skill) => {
+                skill.advances += advances;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skill_value_follows_characteristic_advances() {
+        let mut character = Character {
+            agility: StatValue::new(30),
+            skills: vec![Skill{name: "Dodge".into(), advances: 5, base_attribute: BaseAttribute::Agility}],
+            ..Character::default()
+        };
+        assert!(character.add_characteristic_advances(BaseAttribute::Agility, 5));
+        assert!(character.add_skill_advances("Dodge", 3));
+
+        assert_eq!(character.skill_value(&character.skills[0]), StatValue{initial: 35, advances: 8});
+        assert_eq!(character.skill_value(&character.skills[0]).current(), 43);
+    }
+
+    #[test]
+    fn weapon_damage_and_armour_points() {
+        let mut hand_weapon = Trapping::new("Hand Weapon", 1);
+        hand_weapon.weapon = Some(Weapon { name: "Hand Weapon".into(), group: "Basic".into(), damage: "+SB+4".parse().unwrap(), reach: None,
+                                           range: None, qualities: vec![], flaws: vec![], encumbrance: 1 });
+        let mut mail_shirt = Trapping::new("Sleeved Mail Shirt", 1);
+        mail_shirt.armour = Some(Armour { name: "Sleeved Mail Shirt".into(), locations: vec![HitLocation::Arms, HitLocation::Body],
+                                          armour_points: 2, qualities: vec![], flaws: vec![], encumbrance: 2 });
+        let mut jerkin = Trapping::new("Leather Jerkin", 1);
+        jerkin.armour = Some(Armour { name: "Leather Jerkin".into(), locations: vec![HitLocation::Body], armour_points: 1,
+                                      qualities: vec![], flaws: vec![], encumbrance: 1 });
+        let character = Character { strength: StatValue::new(38), trappings: vec![hand_weapon, mail_shirt, jerkin], ..Character::default() };
+
+        let weapon = character.weapons().next().unwrap();
+        assert_eq!(character.weapon_damage(weapon), 7);
+        assert_eq!(character.armour_points(HitLocation::Head), 0);
+        assert_eq!(character.armour_points(HitLocation::Arms), 2);
+        assert_eq!(character.armour_points(HitLocation::Body), 3);
+    }
+
+    #[test]
+    fn career_history_and_switch_cost() {
+        let step = |career: &str, level: u8, title: &str, completed: bool| CareerStep { career: career.into(), level, title: title.into(), completed };
+        let character = Character { career_history: vec![step("Soldier", 3, "Sergeant", true), step("Outlaw", 2, "Outlaw", false), step("Thief", 1, "Prowler", false)],
+                                    ..Character::default() };
+        assert_eq!(character.formatted_career_history(), "Soldier 3 (Sergeant) → Outlaw 2 → Thief 1 (Prowler)");
+        assert_eq!(character.career_switch_cost(), 300);
+        assert_eq!(Character::default().career_switch_cost(), 0);
+    }
+
+    #[test]
+    fn natural_armour_covers_every_location() {
+        let character = Character { traits: vec![CreatureTrait { rating: Some(2), ..CreatureTrait::new("Armour") }, CreatureTrait::new("Night Vision")],
+                                    ..Character::default() };
+        assert!(character.is_creature() && character.is_armoured());
+        assert!(HitLocation::iterator().all(|location| character.armour_points(*location) == 2));
+    }
+
+    #[test]
+    fn encumbrance_against_strength_and_toughness_bonus() {
+        let rope = Trapping { encumbrance: 1, ..Trapping::new("Rope", 3) };
+        let mut character = Character { strength: StatValue::new(21), toughness: StatValue::new(30), trappings: vec![rope], ..Character::default() };
+        assert_eq!((character.total_encumbrance(), character.encumbrance_limit()), (3, 5));
+        assert!(!character.is_overloaded());
+
+        character.trappings.push(Trapping { encumbrance: 3, ..Trapping::new("Pole", 1) });
+        assert!(character.is_overloaded());
+    }
+
+    #[test]
+    fn advances_on_derived_values_are_rejected() {
+        let mut character = Character::default();
+        assert!(!character.add_characteristic_advances(BaseAttribute::Wounds, 1));
+        assert!(!character.add_skill_advances("Dodge", 1));
+    }
 }
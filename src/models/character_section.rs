@@ -0,0 +1,108 @@
+use std::slice::Iter;
+use std::str::FromStr;
+
+use super::character::Character;
+
+const LIST_SEPARATOR: char = ',';
+
+// Parts of a generated NPC that can be rolled again on their own
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CharacterSection {
+    Characteristics,
+    Skills,
+    Talents,
+    Trappings,
+    Name,
+}
+
+impl CharacterSection {
+    pub fn iterator() -> Iter<'static, CharacterSection> {
+        const SECTIONS: [CharacterSection; 5] = [CharacterSection::Characteristics, CharacterSection::Skills, CharacterSection::Talents,
+                                                 CharacterSection::Trappings, CharacterSection::Name];
+        SECTIONS.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            CharacterSection::Characteristics => "characteristics",
+            CharacterSection::Skills => "skills",
+            CharacterSection::Talents => "talents",
+            CharacterSection::Trappings => "trappings",
+            CharacterSection::Name => "name",
+        }
+    }
+
+    pub fn formatted_list() -> String {
+        CharacterSection::iterator().map(|section| section.to_str()).collect::<Vec<&str>>().join("|")
+    }
+
+    // "characteristics,name"
+    pub fn parse_list(sections: &str) -> Result<Vec<Self>, String> {
+        sections.split(LIST_SEPARATOR).map(str::parse).collect()
+    }
+
+    // Every section but the given ones
+    pub fn except(sections: &[CharacterSection]) -> Vec<Self> {
+        CharacterSection::iterator().filter(|section| !sections.contains(section)).copied().collect()
+    }
+
+    // Spells come with the talents that grant them. Values derived from the section are left to the caller
+    pub fn copy(self, from: &Character, into: &mut Character) {
+        match self {
+            CharacterSection::Characteristics => {
+                into.weapon_skill = from.weapon_skill;
+                into.ballistic_skill = from.ballistic_skill;
+                into.strength = from.strength;
+                into.toughness = from.toughness;
+                into.initiative = from.initiative;
+                into.agility = from.agility;
+                into.dexterity = from.dexterity;
+                into.intelligence = from.intelligence;
+                into.willpower = from.willpower;
+                into.fellowship = from.fellowship;
+            }
+            CharacterSection::Skills => into.skills = from.skills.clone(),
+            CharacterSection::Talents => {
+                into.talents = from.talents.clone();
+                into.spells = from.spells.clone();
+            }
+            CharacterSection::Trappings => into.trappings = from.trappings.clone(),
+            CharacterSection::Name => into.name = from.name.clone(),
+        }
+    }
+}
+
+impl FromStr for CharacterSection {
+    type Err = String;
+
+    fn from_str(section: &str) -> Result<Self, Self::Err> {
+        CharacterSection::iterator()
+            .find(|section_enum| section_enum.to_str() == section)
+            .copied()
+            .ok_or_else(|| format!("invalid section \"{}\"", section))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{stat_value::StatValue, trapping::Trapping};
+
+    #[test]
+    fn section_list() {
+        assert_eq!(CharacterSection::parse_list("characteristics,name"), Ok(vec![CharacterSection::Characteristics, CharacterSection::Name]));
+        assert!(CharacterSection::parse_list("characteristics,hair").is_err());
+        assert_eq!(CharacterSection::except(&[CharacterSection::Skills, CharacterSection::Talents]),
+                   vec![CharacterSection::Characteristics, CharacterSection::Trappings, CharacterSection::Name]);
+    }
+
+    #[test]
+    fn copy_takes_only_the_section() {
+        let from = Character { name: "Gunther".into(), strength: StatValue::new(40), trappings: vec![Trapping::new("Rope", 1)], ..Character::default() };
+        let mut into = Character { name: "Hilda".into(), ..Character::default() };
+        CharacterSection::Characteristics.copy(&from, &mut into);
+        assert_eq!((into.name.as_str(), into.strength.current(), into.trappings.len()), ("Hilda", 40, 0));
+        CharacterSection::Trappings.copy(&from, &mut into);
+        assert_eq!(into.trappings.len(), 1);
+    }
+}
@@ -0,0 +1,29 @@
+use super::{creature_trait::CreatureTrait, generation_base::GenerationBase, size::Size, trapping::Trapping};
+
+// Bestiary entry, rolled without species or career
+#[derive(Debug, Clone)]
+pub struct Creature {
+    pub name: String,
+
+    // attributes
+    pub weapon_skill: GenerationBase,
+    pub ballistic_skill: GenerationBase,
+    pub strength: GenerationBase,
+    pub toughness: GenerationBase,
+    pub initiative: GenerationBase,
+    pub agility: GenerationBase,
+    pub dexterity: GenerationBase,
+    pub intelligence: GenerationBase,
+    pub willpower: GenerationBase,
+    pub fellowship: GenerationBase,
+    // of an Average build, the size changes it
+    pub movement: u32,
+    // taken from the Size trait, Average without one
+    pub size: Size,
+
+    pub traits: Vec<CreatureTrait>,
+    // pool for the creature's random traits
+    pub optional_traits: Vec<CreatureTrait>,
+    pub random_traits: u32,
+    pub trappings: Vec<Trapping>,
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use super::weapon::Damage;
+
+// Bestiary trait such as "Armour 2", "Bite +7", "Size (Small)" or "Ranged +8 (50)"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatureTrait {
+    pub name: String,
+    #[serde(default)]
+    pub specialisation: Option<String>,
+    #[serde(default)]
+    pub rating: Option<u32>,
+    // natural weapons deal damage like any other weapon
+    #[serde(default)]
+    pub damage: Option<Damage>,
+    #[serde(default)]
+    pub range: Option<u32>,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl CreatureTrait {
+    pub fn new(name: &str) -> Self {
+        CreatureTrait { name: name.to_string(), specialisation: None, rating: None, damage: None, range: None, description: String::new() }
+    }
+
+    // Trait as written in a statblock, with damage against the creature's Strength Bonus
+    pub fn formatted(&self, strength_bonus: u32) -> String {
+        let mut formatted = self.name.clone();
+        if let Some(specialisation) = &self.specialisation {
+            formatted.push_str(&format!(" ({})", specialisation));
+        }
+        if let Some(rating) = self.rating {
+            formatted.push_str(&format!(" {}", rating));
+        }
+        if let Some(damage) = self.damage {
+            formatted.push_str(&format!(" +{}", damage.against(strength_bonus)));
+        }
+        if let Some(range) = self.range {
+            formatted.push_str(&format!(" ({})", range));
+        }
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_traits() {
+        assert_eq!(CreatureTrait { rating: Some(2), ..CreatureTrait::new("Armour") }.formatted(3), "Armour 2");
+        assert_eq!(CreatureTrait { damage: "+SB+4".parse().ok(), ..CreatureTrait::new("Bite") }.formatted(3), "Bite +7");
+        assert_eq!(CreatureTrait { specialisation: Some("Small".into()), ..CreatureTrait::new("Size") }.formatted(3), "Size (Small)");
+        assert_eq!(CreatureTrait { damage: "+8".parse().ok(), range: Some(50), ..CreatureTrait::new("Ranged") }.formatted(3), "Ranged +8 (50)");
+        assert_eq!(CreatureTrait::new("Night Vision").formatted(3), "Night Vision");
+    }
+}
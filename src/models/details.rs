@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use super::{generation_base::GenerationBase, roll_table::RollTable};
+
+const INCHES_PER_FOOT: u32 = 12;
+
+// Appearance and background of a character
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Details {
+    pub age: u32,
+    // in inches
+    pub height: u32,
+    pub eyes: String,
+    pub hair: String,
+    pub birthplace: String,
+    pub distinguishing_mark: Option<String>,
+}
+
+impl Details {
+    // 69 inches is 5'9"
+    pub fn formatted_height(&self) -> String {
+        format!("{}'{}\"", self.height / INCHES_PER_FOOT, self.height % INCHES_PER_FOOT)
+    }
+}
+
+// Species tables details are rolled on
+#[derive(Debug, Clone, Deserialize)]
+pub struct DetailTables {
+    pub age: GenerationBase,
+    pub height: GenerationBase,
+    pub eyes: RollTable,
+    pub hair: RollTable,
+    pub birthplace: RollTable,
+    #[serde(default)]
+    pub marks: RollTable,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formatted_height() {
+        let details = Details { age: 20, height: 69, eyes: "Blue".into(), hair: "Black".into(), birthplace: "Altdorf".into(), distinguishing_mark: None };
+        assert_eq!(details.formatted_height(), "5'9\"");
+    }
+}
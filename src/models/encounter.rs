@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+use super::{character::Character, generation_base::GenerationBase};
+
+const ALWAYS: u32 = 100;
+
+// Line of an encounter template such as "2d4 Outlaw 1" or "1 Witch 1 (50%)"
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EncounterMember {
+    // careers as given to generate-npc, e.g. "outlaw_3" or "random_2"
+    pub careers: String,
+    // the encounter's species when not given
+    #[serde(default)]
+    pub species: Option<String>,
+    #[serde(default = "EncounterMember::one")]
+    pub count: GenerationBase,
+    // percentage chance of the line being present at all
+    #[serde(default = "EncounterMember::always")]
+    pub chance: u32,
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+impl EncounterMember {
+    fn one() -> GenerationBase {
+        GenerationBase { base_value: 1, dices: vec![] }
+    }
+
+    fn always() -> u32 {
+        ALWAYS
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Encounter {
+    pub name: String,
+    pub species: String,
+    #[serde(default)]
+    pub affiliation: Option<String>,
+    // every member takes the surname of the first one, e.g. a family business
+    #[serde(default)]
+    pub shared_surname: bool,
+    pub members: Vec<EncounterMember>,
+}
+
+// Generated member of an encounter
+#[derive(Debug, Clone)]
+pub struct GroupMember {
+    pub role: Option<String>,
+    pub character: Character,
+}
+
+impl GroupMember {
+    // Everything after the forename, None for a single name
+    pub fn surname(&self) -> Option<&str> {
+        self.character.name.split_once(' ').map(|(_, surname)| surname)
+    }
+
+    pub fn take_surname(&mut self, surname: &str) {
+        let forename = self.character.name.split_once(' ').map_or(self.character.name.as_str(), |(forename, _)| forename);
+        self.character.name = format!("{} {}", forename, surname);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_member_defaults() {
+        let member: EncounterMember = serde_json::from_str("{\"careers\": \"outlaw_1\", \"count\": \"2d4\"}").unwrap();
+        assert_eq!((member.count, member.chance, member.role), ("2d4".parse().unwrap(), ALWAYS, None));
+        let member: EncounterMember = serde_json::from_str("{\"careers\": \"witch_1\", \"chance\": 50}").unwrap();
+        assert_eq!((member.count, member.chance), ("1".parse().unwrap(), 50));
+    }
+
+    #[test]
+    fn take_surname() {
+        let member = |name: &str| GroupMember { role: None, character: Character { name: name.into(), ..Character::default() } };
+        let leader = member("Gurda of Karak Azgal");
+        let mut follower = member("Thorgrim Ironfist");
+        follower.take_surname(leader.surname().unwrap());
+        assert_eq!(follower.character.name, "Thorgrim of Karak Azgal");
+        assert_eq!(member("Snorri").surname(), None);
+    }
+}
@@ -0,0 +1,39 @@
+use std::slice::Iter;
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Gender {
+    Female,
+    Male,
+    Neutral,
+}
+
+impl Gender {
+    pub fn iterator() -> Iter<'static, Gender> {
+        const GENDERS: [Gender; 3] = [Gender::Female, Gender::Male, Gender::Neutral];
+        GENDERS.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Gender::Female => "female",
+            Gender::Male => "male",
+            Gender::Neutral => "neutral",
+        }
+    }
+
+    pub fn formatted_list() -> String {
+        Gender::iterator().map(|gender| gender.to_str()).collect::<Vec<&str>>().join("|")
+    }
+}
+
+impl FromStr for Gender {
+    type Err = String;
+
+    fn from_str(gender: &str) -> Result<Self, Self::Err> {
+        Gender::iterator()
+            .find(|gender_enum| gender_enum.to_str() == gender)
+            .copied()
+            .ok_or_else(|| format!("invalid gender \"{}\"", gender))
+    }
+}
@@ -1,6 +1,63 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+
 // Representation of N + kDM
-#[derive(PartialEq, Clone, Debug, Hash)]
+#[derive(PartialEq, Clone, Debug, Hash, Deserialize)]
+#[serde(try_from = "String")]
 pub struct GenerationBase {
     pub base_value: u32,
     pub dices: Vec<u32>
 }
+
+// Parses notation used in data files, e.g. "20+2d10", "2d10+20", "d100" or "4"
+impl FromStr for GenerationBase {
+    type Err = String;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let mut base = GenerationBase { base_value: 0, dices: vec![] };
+        for term in notation.split('+').map(str::trim) {
+            let invalid = || format!("invalid generation base \"{}\"", notation);
+            match term.split_once('d') {
+                Some((count, sides)) => {
+                    let count: u32 = if count.is_empty() { 1 } else { count.parse().map_err(|_| invalid())? };
+                    let sides: u32 = sides.parse().map_err(|_| invalid())?;
+                    if sides == 0 {
+                        Err(invalid())?
+                    }
+                    base.dices.extend(std::iter::repeat_n(sides, count as usize));
+                }
+                None => base.base_value += term.parse::<u32>().map_err(|_| invalid())?,
+            }
+        }
+        Ok(base)
+    }
+}
+
+impl TryFrom<String> for GenerationBase {
+    type Error = String;
+
+    fn try_from(notation: String) -> Result<Self, Self::Error> {
+        notation.parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_generation_base() {
+        assert_eq!("20+2d10".parse(), Ok(GenerationBase{base_value: 20, dices: vec![10, 10]}));
+        assert_eq!("2d10 + 20".parse(), Ok(GenerationBase{base_value: 20, dices: vec![10, 10]}));
+        assert_eq!("d100".parse(), Ok(GenerationBase{base_value: 0, dices: vec![100]}));
+        assert_eq!("4".parse(), Ok(GenerationBase{base_value: 4, dices: vec![]}));
+    }
+
+    #[test]
+    fn parse_invalid_generation_base() {
+        assert!("20+xd10".parse::<GenerationBase>().is_err());
+        assert!("2d0".parse::<GenerationBase>().is_err());
+        assert!("".parse::<GenerationBase>().is_err());
+    }
+}
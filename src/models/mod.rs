@@ -1,12 +1,32 @@
+pub mod armour;
 pub mod base_attribute;
+pub mod campaign_npc;
+pub mod career_step;
 pub mod character;
+pub mod character_section;
 pub mod consts;
+pub mod creature;
+pub mod creature_trait;
+pub mod details;
+pub mod encounter;
+pub mod gender;
 pub mod generation_base;
+pub mod name_rules;
+pub mod personality;
 pub mod profession;
 pub mod profession_class;
+pub mod roll_table;
+pub mod saved_npc;
+pub mod size;
 pub mod skill;
 pub mod skill_definition;
+pub mod spell;
+pub mod specialisation;
 pub mod species;
+pub mod stat_value;
+pub mod status;
 pub mod talent;
 pub mod talent_definition;
+pub mod threat;
 pub mod trapping;
+pub mod weapon;
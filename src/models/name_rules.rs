@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::gender::Gender;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GenderedNames {
+    pub female: Vec<String>,
+    pub male: Vec<String>,
+    pub neutral: Vec<String>,
+}
+
+impl GenderedNames {
+    pub fn get(&self, gender: Gender) -> &[String] {
+        match gender {
+            Gender::Female => &self.female,
+            Gender::Male => &self.male,
+            Gender::Neutral => &self.neutral,
+        }
+    }
+}
+
+// Naming customs of a species, names are either taken from the lists or built from syllables.
+// Regional customs come with the regional variants of a species, e.g. "tilean"
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NameRules {
+    pub forenames: GenderedNames,
+    pub prefixes: Vec<String>,
+    pub suffixes: GenderedNames,
+    pub surnames: Vec<String>,
+    pub places: Vec<String>,
+    // keyed by career name, e.g. "Hunter" gives "Jäger"
+    pub career_surnames: HashMap<String, String>,
+    // templates using {surname}, {place} and {career}
+    pub surname_forms: Vec<String>,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+// Roleplaying hooks of a character
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Personality {
+    pub traits: Vec<String>,
+    pub motivation: String,
+    pub short_term_ambition: String,
+    pub long_term_ambition: String,
+    pub secret: String,
+}
+
+// Tables of a career class, traits are shared by all classes
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PersonalityTables {
+    #[serde(default)]
+    pub traits: Vec<String>,
+    pub motivations: Vec<String>,
+    pub short_term_ambitions: Vec<String>,
+    pub long_term_ambitions: Vec<String>,
+    pub secrets: Vec<String>,
+}
@@ -1,12 +1,16 @@
-use super::{trapping::Trapping, skill_definition::SkillDefinition, talent_definition::TalentDefinition, profession_class::ProfessionClass};
+use super::{base_attribute::BaseAttribute, trapping::Trapping, skill_definition::SkillDefinition, talent_definition::TalentDefinition, profession_class::ProfessionClass, status::Status};
 
-// TODO: Fill it with data
+// Single level of a career, holding only what the level adds on top of the previous ones
+#[derive(Debug, Clone)]
 pub struct Profession {
     pub trappings: Vec<Trapping>,
     pub skills: Vec<SkillDefinition>,
     pub talents: Vec<TalentDefinition>,
+    pub characteristics: Vec<BaseAttribute>,
 
     pub profession_level: u8,
     pub name: String,
+    pub title: String,
+    pub status: Status,
     pub class: ProfessionClass,
 }
@@ -1,8 +1,7 @@
-use super::{trapping::Trapping, skill_definition::SkillDefinition, talent_definition::TalentDefinition};
+use super::{personality::PersonalityTables, trapping::Trapping};
 
+#[derive(Debug, Clone)]
 pub struct ProfessionClass {
-    pub name: String,
     pub trappings: Vec<Trapping>,
-    pub skills: Vec<SkillDefinition>,
-    pub talents: Vec<TalentDefinition>,
+    pub personality: Option<PersonalityTables>,
 }
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+
+use super::generation_base::GenerationBase;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RollTableEntry {
+    pub up_to: u32,
+    pub value: String,
+}
+
+// Rules table where each entry covers the rolls above the previous entry up to its own bound, e.g. 01-05, 06-15
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct RollTable {
+    pub entries: Vec<RollTableEntry>,
+}
+
+impl RollTable {
+    // d100 for a d100 table, none for an empty one
+    pub fn dice(&self) -> GenerationBase {
+        let sides = self.entries.iter().map(|entry| entry.up_to).max();
+        GenerationBase { base_value: 0, dices: sides.into_iter().collect() }
+    }
+
+    pub fn lookup(&self, roll: u32) -> Option<&str> {
+        self.entries.iter().find(|entry| roll <= entry.up_to).map(|entry| entry.value.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn table() -> RollTable {
+        RollTable { entries: vec![RollTableEntry { up_to: 5, value: "Grey".into() }, RollTableEntry { up_to: 100, value: "Blue".into() }] }
+    }
+
+    #[test]
+    fn lookup_by_roll() {
+        assert_eq!(table().lookup(1), Some("Grey"));
+        assert_eq!(table().lookup(5), Some("Grey"));
+        assert_eq!(table().lookup(6), Some("Blue"));
+        assert_eq!(table().lookup(101), None);
+    }
+
+    #[test]
+    fn dice_covers_table() {
+        assert_eq!(table().dice(), "d100".parse().unwrap());
+        assert_eq!(RollTable::default().dice(), "0".parse().unwrap());
+    }
+}
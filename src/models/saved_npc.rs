@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use super::character::Character;
+
+// NPC kept in the library along with what it was generated from. The seed regenerates it unless it was changed afterwards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedNpc {
+    pub id: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub seed: Option<u64>,
+    // careers and species as they were given, "random" included
+    pub careers: Vec<String>,
+    pub species: String,
+    // generation options such as "--details" or "--region reikland"
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub character: Character,
+}
+
+impl SavedNpc {
+    // Species and careers are matched by id, so "high_elf" matches "High Elf"
+    pub fn matches(&self, species: Option<&str>, career: Option<&str>, tag: Option<&str>) -> bool {
+        species.is_none_or(|species| Self::id_of(&self.character.species) == species)
+        && career.is_none_or(|career| self.character.career_history.iter().any(|step| Self::id_of(&step.career) == career))
+        && tag.is_none_or(|tag| self.tags.iter().any(|own| own == tag))
+    }
+
+    fn id_of(name: &str) -> String {
+        name.to_lowercase().replace(' ', "_")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::career_step::CareerStep;
+
+    #[test]
+    fn matches_filters() {
+        let character = Character {
+            species: "High Elf".into(),
+            career_history: vec![CareerStep { career: "Witch Hunter".into(), level: 2, title: "Witch Hunter".into(), completed: true }],
+            ..Character::default()
+        };
+        let npc = SavedNpc { id: "ilsa".into(), tags: vec!["altdorf".into()], seed: None, careers: vec![], species: "high_elf".into(), options: vec![], character };
+        assert!(npc.matches(None, None, None));
+        assert!(npc.matches(Some("high_elf"), Some("witch_hunter"), Some("altdorf")));
+        assert!(!npc.matches(Some("dwarf"), None, None));
+        assert!(!npc.matches(None, Some("soldier"), None));
+        assert!(!npc.matches(None, None, Some("nuln")));
+    }
+}
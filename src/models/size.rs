@@ -0,0 +1,156 @@
+use std::{slice::Iter, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+const HIT_MODIFIER_PER_STEP: u32 = 10;
+const MIN_MOVEMENT: u32 = 1;
+
+// Size categories of the bestiary, from smallest to largest
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Size {
+    Tiny,
+    Little,
+    Small,
+    #[default]
+    Average,
+    Large,
+    Enormous,
+    Monstrous,
+}
+
+impl Size {
+    pub fn iterator() -> Iter<'static, Size> {
+        const SIZES: [Size; 7] = [Size::Tiny, Size::Little, Size::Small, Size::Average, Size::Large, Size::Enormous, Size::Monstrous];
+        SIZES.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Size::Tiny => "Tiny",
+            Size::Little => "Little",
+            Size::Small => "Small",
+            Size::Average => "Average",
+            Size::Large => "Large",
+            Size::Enormous => "Enormous",
+            Size::Monstrous => "Monstrous",
+        }
+    }
+
+    // Wounds from the Strength, Toughness and Willpower Bonuses
+    pub fn wounds(self, strength_bonus: u32, toughness_bonus: u32, willpower_bonus: u32) -> u32 {
+        let average = strength_bonus + 2 * toughness_bonus + willpower_bonus;
+        match self {
+            Size::Tiny => 1,
+            Size::Little => toughness_bonus,
+            Size::Small => 2 * toughness_bonus + willpower_bonus,
+            Size::Average => average,
+            Size::Large => 2 * average,
+            Size::Enormous => 4 * average,
+            Size::Monstrous => 8 * average,
+        }
+    }
+
+    // Movement of an Average build, one more for each step above Average and one less for each step below
+    pub fn movement(self, average_movement: u32) -> u32 {
+        match self.cmp(&Size::Average) {
+            std::cmp::Ordering::Equal => average_movement,
+            std::cmp::Ordering::Greater => average_movement + self.steps(Size::Average),
+            std::cmp::Ordering::Less => average_movement.saturating_sub(self.steps(Size::Average)).max(MIN_MOVEMENT),
+        }
+    }
+
+    fn steps(self, other: Size) -> u32 {
+        (self as u32).abs_diff(other as u32)
+    }
+
+    // Hits against a smaller target deal damage multiplied by one more than the steps between the sizes
+    pub fn damage_multiplier(self, target: Size) -> u32 {
+        if self > target { self.steps(target) + 1 } else { 1 }
+    }
+
+    // A larger target is easier to hit
+    pub fn hit_modifier(self, target: Size) -> u32 {
+        if self < target { self.steps(target) * HIT_MODIFIER_PER_STEP } else { 0 }
+    }
+
+    // Modifiers against and from Average foes, empty for Average characters
+    pub fn combat_modifiers(self) -> String {
+        match self.cmp(&Size::Average) {
+            std::cmp::Ordering::Equal => String::new(),
+            std::cmp::Ordering::Greater => format!("damage ×{} against Average foes, who gain +{} to hit",
+                                                   self.damage_multiplier(Size::Average), Size::Average.hit_modifier(self)),
+            std::cmp::Ordering::Less => format!("+{} to hit Average foes, who deal damage ×{}",
+                                                self.hit_modifier(Size::Average), Size::Average.damage_multiplier(self)),
+        }
+    }
+}
+
+impl std::fmt::Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(size: &str) -> Result<Self, Self::Err> {
+        Size::iterator()
+            .find(|size_enum| size_enum.to_str() == size)
+            .copied()
+            .ok_or(format!("invalid size \"{}\"", size))
+    }
+}
+
+impl From<Size> for String {
+    fn from(size: Size) -> Self {
+        size.to_str().to_string()
+    }
+}
+
+impl TryFrom<String> for Size {
+    type Error = String;
+
+    fn try_from(size: String) -> Result<Self, Self::Error> {
+        size.parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wounds_by_size() {
+        // SB 3, TB 4, WPB 2
+        let wounds: Vec<u32> = Size::iterator().map(|size| size.wounds(3, 4, 2)).collect();
+        assert_eq!(wounds, vec![1, 4, 10, 13, 26, 52, 104]);
+    }
+
+    #[test]
+    fn movement_by_size() {
+        let movement: Vec<u32> = Size::iterator().map(|size| size.movement(4)).collect();
+        assert_eq!(movement, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(Size::Tiny.movement(2), 1);
+    }
+
+    #[test]
+    fn combat_modifiers_by_size() {
+        assert_eq!(Size::Large.damage_multiplier(Size::Average), 2);
+        assert_eq!(Size::Monstrous.damage_multiplier(Size::Small), 5);
+        assert_eq!(Size::Small.damage_multiplier(Size::Average), 1);
+        assert_eq!(Size::Tiny.hit_modifier(Size::Average), 30);
+        assert_eq!(Size::Enormous.hit_modifier(Size::Average), 0);
+
+        assert_eq!(Size::Average.combat_modifiers(), "");
+        assert_eq!(Size::Large.combat_modifiers(), "damage ×2 against Average foes, who gain +10 to hit");
+        assert_eq!(Size::Little.combat_modifiers(), "+20 to hit Average foes, who deal damage ×3");
+    }
+
+    #[test]
+    fn parse_size() {
+        assert_eq!("Enormous".parse(), Ok(Size::Enormous));
+        assert!("Huge".parse::<Size>().is_err());
+    }
+}
@@ -1,7 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use super::base_attribute::BaseAttribute;
-#[derive(Debug)]
+
+// Skill value is derived from the current base characteristic, so only the advances are stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skill {
     pub name: String,
-    pub value: u32,
+    pub advances: u32,
     pub base_attribute: BaseAttribute,
 }
@@ -1,6 +1,9 @@
 use super::base_attribute::BaseAttribute;
 
+#[derive(Debug, Clone)]
 pub struct SkillDefinition {
     pub name: String,
     pub base_attribute: BaseAttribute,
+    // what an "(Any)" skill can be taken as
+    pub specialisations: Vec<String>,
 }
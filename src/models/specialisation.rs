@@ -0,0 +1,34 @@
+// Grouped skills and talents are named "Base (Specialisation)", e.g. "Melee (Basic)" or "Acute Sense (Sight)"
+pub const ANY_SPECIALISATION: &str = "Any";
+
+pub fn specialisation(name: &str) -> Option<&str> {
+    name.split_once(" (").and_then(|(_, rest)| rest.strip_suffix(')'))
+}
+
+pub fn base_name(name: &str) -> &str {
+    name.split(" (").next().unwrap_or(name)
+}
+
+// "(Any)" is left for the generator to choose
+pub fn is_any(name: &str) -> bool {
+    specialisation(name) == Some(ANY_SPECIALISATION)
+}
+
+pub fn specialised(name: &str, specialisation: &str) -> String {
+    format!("{} ({})", base_name(name), specialisation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names_with_specialisation() {
+        assert_eq!(specialisation("Melee (Two-handed)"), Some("Two-handed"));
+        assert_eq!(base_name("Melee (Two-handed)"), "Melee");
+        assert_eq!(specialisation("Cool"), None);
+        assert_eq!(base_name("Cool"), "Cool");
+        assert!(is_any("Trade (Any)"));
+        assert_eq!(specialised("Trade (Any)", "Cook"), "Trade (Cook)");
+    }
+}
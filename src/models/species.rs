@@ -1,5 +1,6 @@
-use super::{generation_base::GenerationBase, trapping::Trapping, talent::Talent, skill::Skill};
+use super::{base_attribute::BaseAttribute, details::DetailTables, generation_base::GenerationBase, name_rules::NameRules, size::Size, trapping::Trapping, talent::Talent, skill_definition::SkillDefinition};
 
+#[derive(Debug, Clone)]
 pub struct Species {
     pub name: String,
 
@@ -14,10 +15,42 @@ pub struct Species {
     pub intelligence: GenerationBase,
     pub willpower: GenerationBase,
     pub fellowship: GenerationBase,
-    pub wounds: GenerationBase,
+    // of an Average build, the size changes it
     pub movement: u32,
+    pub size: Size,
+
+    pub fate: u32,
+    pub resilience: u32,
+    // spread randomly between Fate and Resilience
+    pub extra_points: u32,
 
     pub trappings: Vec<Trapping>,
+    // one talent is taken from each group of alternatives
+    pub talents: Vec<Vec<Talent>>,
+    // pool for the species' random talents
     pub possible_talents: Vec<Talent>,
-    pub possible_skills: Vec<Skill>,
+    pub random_talents: u32,
+    pub possible_skills: Vec<SkillDefinition>,
+
+    pub names: NameRules,
+    pub details: Option<DetailTables>,
+}
+
+impl Species {
+    // Dice rolled for a characteristic, Wounds and Movement are not rolled
+    pub fn characteristic(&self, attribute: BaseAttribute) -> Option<&GenerationBase> {
+        match attribute {
+            BaseAttribute::WeaponSkill => Some(&self.weapon_skill),
+            BaseAttribute::BallisticSkill => Some(&self.ballistic_skill),
+            BaseAttribute::Strength => Some(&self.strength),
+            BaseAttribute::Toughness => Some(&self.toughness),
+            BaseAttribute::Initiative => Some(&self.initiative),
+            BaseAttribute::Agility => Some(&self.agility),
+            BaseAttribute::Dexterity => Some(&self.dexterity),
+            BaseAttribute::Intelligence => Some(&self.intelligence),
+            BaseAttribute::Willpower => Some(&self.willpower),
+            BaseAttribute::Fellowship => Some(&self.fellowship),
+            BaseAttribute::Wounds | BaseAttribute::Movement => None,
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use super::{base_attribute::BaseAttribute, character::Character};
+
+// Blessings and Miracles are prayed for, so only spells have a Casting Number
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Spell {
+    pub name: String,
+    #[serde(default)]
+    pub cn: Option<u32>,
+    pub range: String,
+    pub target: String,
+    pub duration: String,
+    pub effect: String,
+}
+
+// Lore of magic or deity, named by the talent's specialisation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lore {
+    pub name: String,
+    pub spells: Vec<Spell>,
+}
+
+// How many spells of a lore a caster knows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpellsKnown {
+    All,
+    Count(u32),
+    Bonus(BaseAttribute),
+}
+
+impl SpellsKnown {
+    pub fn count(self, character: &Character) -> u32 {
+        match self {
+            SpellsKnown::All => u32::MAX,
+            SpellsKnown::Count(count) => count,
+            SpellsKnown::Bonus(attribute) => character.characteristic(attribute).map_or(0, |characteristic| characteristic.bonus()),
+        }
+    }
+}
+
+// Lores opened up by a talent such as "Arcane Magic (Fire)"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MagicGrant {
+    pub known: SpellsKnown,
+    pub lores: Vec<Lore>,
+}
+
+impl MagicGrant {
+    pub fn lore(&self, name: &str) -> Option<&Lore> {
+        self.lores.iter().find(|lore| lore.name == name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::stat_value::StatValue;
+
+    #[test]
+    fn spells_known_by_characteristic_bonus() {
+        let character = Character { intelligence: StatValue::new(43), ..Character::default() };
+        assert_eq!(SpellsKnown::Bonus(BaseAttribute::Intelligence).count(&character), 4);
+        assert_eq!(SpellsKnown::Count(1).count(&character), 1);
+        assert_eq!(SpellsKnown::All.count(&character), u32::MAX);
+    }
+
+    #[test]
+    fn parse_spells_known() {
+        assert_eq!(serde_json::from_str::<SpellsKnown>("\"all\"").unwrap(), SpellsKnown::All);
+        assert_eq!(serde_json::from_str::<SpellsKnown>("{\"count\": 1}").unwrap(), SpellsKnown::Count(1));
+        assert_eq!(serde_json::from_str::<SpellsKnown>("{\"bonus\": \"Willpower\"}").unwrap(), SpellsKnown::Bonus(BaseAttribute::Willpower));
+    }
+}
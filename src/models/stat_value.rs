@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+// Initial value plus bought advances, as written on the character sheet
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct StatValue {
+    pub initial: u32,
+    pub advances: u32,
+}
+
+impl StatValue {
+    pub fn new(initial: u32) -> Self {
+        StatValue { initial, advances: 0 }
+    }
+
+    pub fn current(&self) -> u32 {
+        self.initial + self.advances
+    }
+
+    pub fn bonus(&self) -> u32 {
+        self.current() / 10
+    }
+}
@@ -0,0 +1,114 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::generation_base::GenerationBase;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StatusTier {
+    Brass,
+    Silver,
+    Gold,
+}
+
+impl StatusTier {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            StatusTier::Brass => "Brass",
+            StatusTier::Silver => "Silver",
+            StatusTier::Gold => "Gold",
+        }
+    }
+
+    pub fn coin(self) -> &'static str {
+        match self {
+            StatusTier::Brass => "Brass Pennies",
+            StatusTier::Silver => "Silver Shillings",
+            StatusTier::Gold => "Gold Crowns",
+        }
+    }
+}
+
+// Social standing of a career level, e.g. "Silver 2"
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Status {
+    pub tier: StatusTier,
+    pub standing: u32,
+}
+
+impl Status {
+    // Coin earned per standing: Brass 2d10 pennies, Silver 1d10 shillings, Gold 1 crown
+    pub fn income(&self) -> GenerationBase {
+        match self.tier {
+            StatusTier::Brass => GenerationBase { base_value: 0, dices: vec![10; 2 * self.standing as usize] },
+            StatusTier::Silver => GenerationBase { base_value: 0, dices: vec![10; self.standing as usize] },
+            StatusTier::Gold => GenerationBase { base_value: self.standing, dices: vec![] },
+        }
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.tier.to_str(), self.standing)
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid status \"{}\"", notation);
+        let (tier, standing) = notation.trim().split_once(' ').ok_or_else(invalid)?;
+        let tier = match tier {
+            "Brass" => StatusTier::Brass,
+            "Silver" => StatusTier::Silver,
+            "Gold" => StatusTier::Gold,
+            _ => Err(invalid())?,
+        };
+        let standing: u32 = standing.trim().parse().map_err(|_| invalid())?;
+        if standing == 0 {
+            Err(invalid())?
+        }
+        Ok(Status { tier, standing })
+    }
+}
+
+impl From<Status> for String {
+    fn from(status: Status) -> Self {
+        status.to_string()
+    }
+}
+
+impl TryFrom<String> for Status {
+    type Error = String;
+
+    fn try_from(notation: String) -> Result<Self, Self::Error> {
+        notation.parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_status() {
+        assert_eq!("Silver 2".parse(), Ok(Status { tier: StatusTier::Silver, standing: 2 }));
+        assert_eq!("Gold 1".parse::<Status>().unwrap().to_string(), "Gold 1");
+    }
+
+    #[test]
+    fn parse_invalid_status() {
+        assert!("Copper 2".parse::<Status>().is_err());
+        assert!("Brass".parse::<Status>().is_err());
+        assert!("Brass 0".parse::<Status>().is_err());
+    }
+
+    #[test]
+    fn income_by_tier() {
+        assert_eq!(Status { tier: StatusTier::Brass, standing: 2 }.income(), "4d10".parse().unwrap());
+        assert_eq!(Status { tier: StatusTier::Silver, standing: 2 }.income(), "2d10".parse().unwrap());
+        assert_eq!(Status { tier: StatusTier::Gold, standing: 3 }.income(), "3".parse().unwrap());
+    }
+}
@@ -1,7 +1,61 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+use super::{specialisation, spell::MagicGrant, talent_definition::TalentDefinition};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Talent {
     pub name: String,
     pub level: u32,
     pub max_level: u32,
     pub description: String,
+    // lores a spellcasting talent opens up
+    pub magic: Option<MagicGrant>,
+    // what an "(Any)" talent can be taken as
+    #[serde(default)]
+    pub specialisations: Vec<String>,
+}
+
+impl Talent {
+    // "Arcane Magic (Fire)" is specialised in "Fire"
+    pub fn specialisation(&self) -> Option<&str> {
+        specialisation::specialisation(&self.name)
+    }
+
+    pub fn is_any_specialisation(&self) -> bool {
+        specialisation::is_any(&self.name)
+    }
+
+    pub fn specialised(&self, specialisation: &str) -> Talent {
+        Talent { name: specialisation::specialised(&self.name, specialisation), ..self.clone() }
+    }
+}
+
+impl From<&TalentDefinition> for Talent {
+    fn from(definition: &TalentDefinition) -> Self {
+        Talent {
+            name: definition.name.clone(),
+            level: definition.level,
+            max_level: definition.max_level,
+            description: definition.description.clone(),
+            magic: definition.magic.clone(),
+            specialisations: definition.specialisations.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn talent(name: &str) -> Talent {
+        Talent { name: name.into(), level: 1, max_level: 1, description: "Desc".into(), magic: None, specialisations: vec![] }
+    }
+
+    #[test]
+    fn talent_specialisation() {
+        assert_eq!(talent("Arcane Magic (Fire)").specialisation(), Some("Fire"));
+        assert_eq!(talent("Petty Magic").specialisation(), None);
+        assert!(talent("Bless (Any)").is_any_specialisation());
+        assert_eq!(talent("Bless (Any)").specialised("Sigmar").name, "Bless (Sigmar)");
+    }
 }
@@ -1,6 +1,11 @@
+use super::spell::MagicGrant;
+
+#[derive(Debug, Clone)]
 pub struct TalentDefinition {
     pub name: String,
     pub level: u32,
     pub max_level: u32,
     pub description: String,
+    pub magic: Option<MagicGrant>,
+    pub specialisations: Vec<String>,
 }
@@ -0,0 +1,210 @@
+use std::slice::Iter;
+
+use super::{armour::HitLocation, character::Character, weapon::Damage};
+
+const UNARMED: Damage = Damage { strength_bonus: true, modifier: 0 };
+
+// Single attack as the chance to hit in percent and the damage of a hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attack {
+    pub chance: u32,
+    pub damage: u32,
+}
+
+impl Attack {
+    pub fn expected_damage(&self) -> f64 {
+        f64::from(self.chance.min(100)) / 100.0 * f64::from(self.damage)
+    }
+
+    // Weapons use their Melee or Ranged skill, natural weapons and fists fall back to WS or BS
+    pub fn best_of(character: &Character) -> Attack {
+        let strength_bonus = character.strength.bonus();
+        let skill_or = |skill: String, fallback: u32| character.skills
+                                                               .iter()
+                                                               .find(|known| known.name == skill)
+                                                               .map_or(fallback, |known| character.skill_value(known).current());
+        let weapons = character.weapons().map(|weapon| match weapon.range {
+            Some(_) => Attack { chance: skill_or(format!("Ranged ({})", weapon.group), character.ballistic_skill.current()), damage: character.weapon_damage(weapon) },
+            None => Attack { chance: skill_or(format!("Melee ({})", weapon.group), character.weapon_skill.current()), damage: character.weapon_damage(weapon) },
+        });
+        let natural = character.traits.iter().filter_map(|creature_trait| {
+            let chance = if creature_trait.range.is_some() { character.ballistic_skill.current() } else { character.weapon_skill.current() };
+            creature_trait.damage.map(|damage| Attack { chance, damage: damage.against(strength_bonus) })
+        });
+        let unarmed = Attack { chance: character.weapon_skill.current(), damage: UNARMED.against(strength_bonus) };
+
+        weapons.chain(natural)
+               .fold(unarmed, |best, attack| if attack.expected_damage() > best.expected_damage() { attack } else { best })
+    }
+}
+
+// Combat strength of one side of a fight
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupThreat {
+    pub members: usize,
+    pub total_wounds: u32,
+    pub average_weapon_skill: f64,
+    pub average_ballistic_skill: f64,
+    // Armour Points averaged over locations
+    pub average_armour: f64,
+    pub average_toughness_bonus: f64,
+    // expected damage per round before Toughness and armour
+    pub damage_output: f64,
+    attacks: Vec<Attack>,
+}
+
+impl GroupThreat {
+    pub fn of(characters: &[Character]) -> Self {
+        let average = |value: &dyn Fn(&Character) -> f64| match characters.len() {
+            0 => 0.0,
+            len => characters.iter().map(value).sum::<f64>() / len as f64,
+        };
+        let locations = HitLocation::iterator().len() as f64;
+        let attacks: Vec<Attack> = characters.iter().map(Attack::best_of).collect();
+
+        GroupThreat {
+            members: characters.len(),
+            total_wounds: characters.iter().map(|character| character.wounds).sum(),
+            average_weapon_skill: average(&|character| f64::from(character.weapon_skill.current())),
+            average_ballistic_skill: average(&|character| f64::from(character.ballistic_skill.current())),
+            average_armour: average(&|character| HitLocation::iterator().map(|location| f64::from(character.armour_points(*location))).sum::<f64>() / locations),
+            average_toughness_bonus: average(&|character| f64::from(character.toughness.bonus())),
+            damage_output: attacks.iter().map(Attack::expected_damage).sum(),
+            attacks,
+        }
+    }
+
+    // Every hit deals at least 1 Wound once the defenders' Toughness Bonus and armour are taken off
+    pub fn damage_against(&self, defenders: &GroupThreat) -> f64 {
+        let soak = defenders.average_toughness_bonus + defenders.average_armour;
+        self.attacks
+            .iter()
+            .map(|attack| f64::from(attack.chance.min(100)) / 100.0 * (f64::from(attack.damage) - soak).max(1.0))
+            .sum()
+    }
+
+    pub fn rounds_to_defeat(&self, defenders: &GroupThreat) -> f64 {
+        match self.damage_against(defenders) {
+            damage if damage > 0.0 => f64::from(defenders.total_wounds) / damage,
+            _ => f64::INFINITY,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Trivial,
+    Easy,
+    Challenging,
+    Hard,
+    Deadly,
+}
+
+impl Difficulty {
+    pub fn iterator() -> Iter<'static, Difficulty> {
+        const DIFFICULTIES: [Difficulty; 5] = [Difficulty::Trivial, Difficulty::Easy, Difficulty::Challenging, Difficulty::Hard, Difficulty::Deadly];
+        DIFFICULTIES.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Difficulty::Trivial => "Trivial",
+            Difficulty::Easy => "Easy",
+            Difficulty::Challenging => "Challenging",
+            Difficulty::Hard => "Hard",
+            Difficulty::Deadly => "Deadly",
+        }
+    }
+
+    // Rounds the party needs to win for every round the group needs to
+    fn from_ratio(ratio: f64) -> Self {
+        const UP_TO: [f64; 4] = [0.25, 0.5, 1.0, 2.0];
+        Difficulty::iterator()
+            .zip(UP_TO)
+            .find(|(_, up_to)| ratio <= *up_to)
+            .map_or(Difficulty::Deadly, |(difficulty, _)| *difficulty)
+    }
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+// Rough threat of a group against a party of PCs, both sides trading their best attacks every round
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assessment {
+    pub party: GroupThreat,
+    pub group: GroupThreat,
+    pub rounds_to_defeat_group: f64,
+    pub rounds_to_defeat_party: f64,
+    pub difficulty: Difficulty,
+}
+
+impl Assessment {
+    pub fn new(party: &[Character], group: &[Character]) -> Self {
+        let party = GroupThreat::of(party);
+        let group = GroupThreat::of(group);
+        let rounds_to_defeat_group = party.rounds_to_defeat(&group);
+        let rounds_to_defeat_party = group.rounds_to_defeat(&party);
+        // an empty group is no threat, two sides unable to hurt each other are an even match
+        let ratio = if rounds_to_defeat_group == 0.0 {
+            0.0
+        }
+        else if rounds_to_defeat_group.is_infinite() && rounds_to_defeat_party.is_infinite() {
+            1.0
+        }
+        else {
+            rounds_to_defeat_group / rounds_to_defeat_party
+        };
+        Assessment { party, group, rounds_to_defeat_group, rounds_to_defeat_party, difficulty: Difficulty::from_ratio(ratio) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{base_attribute::BaseAttribute, creature_trait::CreatureTrait, skill::Skill, stat_value::StatValue, trapping::Trapping, weapon::Weapon};
+
+    fn fighter(weapon_skill: u32, wounds: u32) -> Character {
+        Character { weapon_skill: StatValue::new(weapon_skill), strength: StatValue::new(30), toughness: StatValue::new(30), wounds, ..Character::default() }
+    }
+
+    fn hand_weapon() -> Trapping {
+        let weapon = Weapon { name: "Hand Weapon".into(), group: "Basic".into(), damage: "+SB+4".parse().unwrap(), reach: Some("Average".into()),
+                              range: None, qualities: vec![], flaws: vec![], encumbrance: 1 };
+        Trapping { weapon: Some(weapon), ..Trapping::new("Hand Weapon", 1) }
+    }
+
+    #[test]
+    fn best_attack_uses_weapon_skill() {
+        assert_eq!(Attack::best_of(&fighter(40, 10)), Attack { chance: 40, damage: 3 });
+
+        let mut soldier = Character { trappings: vec![hand_weapon()], ..fighter(40, 10) };
+        soldier.skills.push(Skill { name: "Melee (Basic)".into(), advances: 10, base_attribute: BaseAttribute::WeaponSkill });
+        assert_eq!(Attack::best_of(&soldier), Attack { chance: 50, damage: 7 });
+
+        let wolf = Character { traits: vec![CreatureTrait { damage: "+SB+3".parse().ok(), ..CreatureTrait::new("Bite") }], ..fighter(35, 10) };
+        assert_eq!(Attack::best_of(&wolf), Attack { chance: 35, damage: 6 });
+    }
+
+    #[test]
+    fn group_threat_totals_and_averages() {
+        let group = GroupThreat::of(&[fighter(40, 10), fighter(20, 14)]);
+        assert_eq!((group.members, group.total_wounds, group.average_weapon_skill, group.average_toughness_bonus), (2, 24, 30.0, 3.0));
+        assert!((group.damage_output - 1.8).abs() < 1e-9);
+        assert_eq!(GroupThreat::of(&[]).total_wounds, 0);
+    }
+
+    #[test]
+    fn difficulty_follows_the_odds() {
+        let party = [fighter(50, 12), fighter(50, 12), fighter(50, 12), fighter(50, 12)];
+        assert_eq!(Assessment::new(&party, &[fighter(30, 8)]).difficulty, Difficulty::Trivial);
+        assert_eq!(Assessment::new(&party, &[]).difficulty, Difficulty::Trivial);
+        let horde: Vec<Character> = (0..12).map(|_| fighter(50, 14)).collect();
+        assert_eq!(Assessment::new(&party, &horde).difficulty, Difficulty::Deadly);
+        let even = Assessment::new(&party, &party);
+        assert_eq!((even.difficulty, even.rounds_to_defeat_group), (Difficulty::Challenging, even.rounds_to_defeat_party));
+    }
+}
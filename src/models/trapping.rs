@@ -1,2 +1,40 @@
-#[derive(Debug)]
-pub struct Trapping { pub name: String, pub count: u32 }
+use serde::{Deserialize, Serialize};
+
+use super::{armour::Armour, weapon::Weapon};
+
+// Weapons and armour carry their rules data along with the name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trapping {
+    pub name: String,
+    pub count: u32,
+    pub weapon: Option<Weapon>,
+    pub armour: Option<Armour>,
+
+    // per item
+    pub encumbrance: u32,
+    // encumbrance a container can hold, 0 for anything that is not a container
+    pub carries: u32,
+    // worn items are never packed away
+    pub worn: bool,
+    // name of the container the item is packed in
+    pub packed_in: Option<String>,
+}
+
+impl Trapping {
+    pub fn new(name: &str, count: u32) -> Self {
+        Trapping { name: name.to_string(), count, weapon: None, armour: None, encumbrance: 0, carries: 0, worn: false, packed_in: None }
+    }
+
+    pub fn total_encumbrance(&self) -> u32 {
+        self.encumbrance * self.count
+    }
+
+    pub fn is_container(&self) -> bool {
+        self.carries > 0
+    }
+
+    // Loose gear that may go into a container
+    pub fn is_packable(&self) -> bool {
+        !self.worn && !self.is_container() && self.weapon.is_none() && self.armour.is_none()
+    }
+}
@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+const STRENGTH_BONUS: &str = "SB";
+
+// Damage as written in the rules, "+SB+4" adds the wielder's Strength Bonus, "+9" does not
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Damage {
+    pub strength_bonus: bool,
+    pub modifier: u32,
+}
+
+impl Damage {
+    pub fn against(&self, strength_bonus: u32) -> u32 {
+        if self.strength_bonus { strength_bonus + self.modifier } else { self.modifier }
+    }
+}
+
+impl std::fmt::Display for Damage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.strength_bonus, self.modifier) {
+            (true, 0) => write!(f, "+{}", STRENGTH_BONUS),
+            (true, modifier) => write!(f, "+{}+{}", STRENGTH_BONUS, modifier),
+            (false, modifier) => write!(f, "+{}", modifier),
+        }
+    }
+}
+
+impl FromStr for Damage {
+    type Err = String;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid damage \"{}\"", notation);
+        let terms: Vec<&str> = notation.split('+').map(str::trim).filter(|term| !term.is_empty()).collect();
+        let strength_bonus = terms.first() == Some(&STRENGTH_BONUS);
+        let modifier = match &terms[usize::from(strength_bonus)..] {
+            [] if strength_bonus => 0,
+            [modifier] => modifier.parse().map_err(|_| invalid())?,
+            _ => Err(invalid())?,
+        };
+        Ok(Damage { strength_bonus, modifier })
+    }
+}
+
+impl From<Damage> for String {
+    fn from(damage: Damage) -> Self {
+        damage.to_string()
+    }
+}
+
+impl TryFrom<String> for Damage {
+    type Error = String;
+
+    fn try_from(notation: String) -> Result<Self, Self::Error> {
+        notation.parse()
+    }
+}
+
+// Melee weapons have a reach, ranged ones a range in yards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Weapon {
+    pub name: String,
+    pub group: String,
+    pub damage: Damage,
+    #[serde(default)]
+    pub reach: Option<String>,
+    #[serde(default)]
+    pub range: Option<u32>,
+    #[serde(default)]
+    pub qualities: Vec<String>,
+    #[serde(default)]
+    pub flaws: Vec<String>,
+    #[serde(default)]
+    pub encumbrance: u32,
+}
+
+impl Weapon {
+    pub fn formatted_reach(&self) -> String {
+        match (&self.reach, self.range) {
+            (Some(reach), _) => reach.clone(),
+            (None, Some(range)) => format!("{} yd", range),
+            (None, None) => String::new(),
+        }
+    }
+
+    pub fn traits(&self) -> Vec<&str> {
+        self.qualities.iter().chain(self.flaws.iter()).map(String::as_str).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_damage() {
+        assert_eq!("+SB+4".parse(), Ok(Damage { strength_bonus: true, modifier: 4 }));
+        assert_eq!("+SB".parse(), Ok(Damage { strength_bonus: true, modifier: 0 }));
+        assert_eq!("+9".parse(), Ok(Damage { strength_bonus: false, modifier: 9 }));
+        assert!("+SB+x".parse::<Damage>().is_err());
+        assert!("".parse::<Damage>().is_err());
+    }
+
+    #[test]
+    fn damage_against_strength_bonus() {
+        assert_eq!("+SB+4".parse::<Damage>().unwrap().against(3), 7);
+        assert_eq!("+9".parse::<Damage>().unwrap().against(3), 9);
+        assert_eq!("+SB+4".parse::<Damage>().unwrap().to_string(), "+SB+4");
+    }
+}
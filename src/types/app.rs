@@ -1,23 +1,56 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+
+use rustyline::{error::ReadlineError, history::DefaultHistory, Editor};
+
+use crate::interfaces::{character_displayer::CharacterDisplayer, character_generator::CharacterGenerator, creature_generator::CreatureGenerator, npc_store::NpcStore, random_provider::RandomProvider, resource_bag::ResourceBag};
+use crate::models::{character::Character, character_section::CharacterSection, creature::Creature, encounter::{Encounter, GroupMember}, generation_base::GenerationBase, profession::Profession, saved_npc::SavedNpc, skill_definition::SkillDefinition, species::Species, talent::Talent, threat::Assessment};
 use crate::types::command::Command;
 
+use super::assessment_displayer::AssessmentDisplayer;
+use super::campaign::Campaign;
 use super::command::CommandError;
+use super::command_option::{CommandOption, CommandOptions, TAG_SEPARATOR};
+use super::console_settings::ConsoleSettings;
+use super::display_format::DisplayFormat;
+use super::encounter_displayer::EncounterDisplayer;
+use super::game_data::GameData;
+use super::generate_npc_command_args::{GenerateNpcCommandArgs, RANDOM_SPECIES};
+use super::npc_library::NpcLibrary;
+use super::random_character_generator::RandomCharacterGenerator;
+use super::repl::{ReplCommand, ReplHelper, ReplSession, REPL_PROMPT};
+use super::seeded_random_provider::SeededRandomProvider;
+use super::thread_based_random_provider::ThreadBasedRandomProvider;
 const USAGE_MESSAGE: &str = "MagicalName usage <command> [args]";
+const DEFAULT_REGION: &str = "reikland";
+// PCs are assessed as humans, their careers matter far more than their species
+const PARTY_SPECIES: &str = "human";
+const HISTORY_FILE: &str = ".wfrp-npc-generator-history";
+// Options a saved NPC was generated with, kept so it can be generated again
+const GENERATION_OPTIONS: [CommandOption; 9] = [CommandOption::Income, CommandOption::Name, CommandOption::Gender, CommandOption::Details,
+                                                CommandOption::WithPersonality, CommandOption::Prune, CommandOption::Pack, CommandOption::Class,
+                                                CommandOption::Region];
+// Stream of the seed's provider the unlocked sections of a locked NPC are rolled with
+const REROLL_STREAM: u64 = 1;
 
-pub struct App {}
+pub struct App {
+    game_data: GameData,
+}
 
 impl App {
     pub fn new()->Self {
-        App{}
+        App{game_data: GameData::embedded()}
     }
 
     pub fn run(&self, args: impl Iterator<Item=String>)->Result<(), CommandError> {
         let args = args.skip(1) //first item is 'most of the time' the name of the executable
                                     .collect();
 
-        match Command::parse_command(&args, None) {
-            Ok(_) => {
-                todo!("implement acutal app")
-            }
+        match Command::parse_command(&args, None).and_then(|command| self.execute(command, &args)) {
+            Ok(_) => Ok(()),
             Err(e) => {
                 match e {
                     CommandError::EmptyCommand => {
@@ -25,10 +58,10 @@ impl App {
                         Self::print_available_commands();
                     }
                     CommandError::InvalidCommand => {
-                        println!("Command \"{}\" is not recognized", args.first().map_or(" ", |s| s));
+                        println!("Command \"{}\" is not recognized", Self::typed_command(&args));
                         Self::print_available_commands();
                     }
-                    _ => println!("{}", e)
+                    e => self.print_error(e),
                 }
                 Err(e)?
             }
@@ -36,6 +69,650 @@ impl App {
         
     }
 
+    // The command and, for a command with subcommands, the subcommand as typed
+    fn typed_command(args: &Vec<String>)->String {
+        let positional = CommandOptions(args).positional();
+        let words = match positional.first().map(|command| Command::from_str(command)) {
+            Some(Ok(command)) if !command.subcommands().is_empty() => 2,
+            _ => 1,
+        };
+        positional.iter().take(words).map(|word| word.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    // Errors naming unknown data list what is available
+    fn print_error(&self, e: CommandError) {
+        match e {
+            CommandError::UnknownProfession => {
+                println!("{}, available professions: {}", e, self.game_data.career_ids().join(", "));
+            }
+            CommandError::UnknownSpecies => {
+                println!("{}, available species: {}", e, self.game_data.species_ids().join(", "));
+            }
+            CommandError::UnknownRegion => {
+                println!("{}, available regions: {}", e, self.game_data.region_ids().join(", "));
+            }
+            CommandError::UnknownClass => {
+                println!("{}, available classes: {}", e, self.game_data.class_ids().join(", "));
+            }
+            CommandError::UnknownEncounter => {
+                println!("{}, available encounters: {}", e, self.game_data.encounter_ids().join(", "));
+            }
+            CommandError::UnknownCreature => {
+                println!("{}, available creatures: {}", e, self.game_data.creature_ids().join(", "));
+            }
+            _ => println!("{}", e)
+        }
+    }
+
+    fn execute(&self, command: Command, args: &Vec<String>)->Result<(), CommandError> {
+        match command {
+            Command::GenerateNpc => self.generate_npc(args),
+            Command::GenerateCreature => self.generate_creature(args),
+            Command::GenerateBatch => self.generate_npc(&Command::batch_as_generate_npc(args)),
+            Command::Encounter => self.encounter(args),
+            Command::Assess => self.assess(args),
+            Command::Repl => self.repl(args),
+            Command::List => Self::list(args),
+            Command::Show => Self::show(args),
+            Command::Delete => Self::delete(args),
+            // Parsing resolves "npc" to one of its subcommands
+            Command::Npc => Err(CommandError::InvalidCommand),
+            Command::Search => Self::search(args),
+            Command::Tag => Self::tag(args),
+            Command::Annotate => Self::annotate(args),
+            Command::Relate => Self::relate(args),
+        }
+    }
+
+    fn generate_npc(&self, args: &Vec<String>)->Result<(), CommandError> {
+        self.generate_npc_to(args, &mut |text| println!("{}", text))
+    }
+
+    // Everything generate-npc prints goes through print, a batch one NPC at a time as they are done
+    fn generate_npc_to(&self, args: &Vec<String>, print: &mut dyn FnMut(String))->Result<(), CommandError> {
+        let npc_args = GenerateNpcCommandArgs(args);
+        let options = CommandOptions(args);
+        let arg_offset = Some(1);
+        let count = options.value(CommandOption::Count)
+                           .map_or(Ok(1), |count| count.parse().map_err(|_| CommandError::InvalidArguments))?;
+
+        self.validate_tables(&options)?;
+        if options.is_set(CommandOption::Save) && count > 1 {
+            Err(CommandError::InvalidArguments)?
+        }
+        self.generate_npcs_with(Self::random_provider(&options)?, &options, &npc_args, arg_offset, count, print)
+    }
+
+    // Class and region only matter for random careers and species, but are checked up front
+    fn validate_tables(&self, options: &CommandOptions)->Result<(), CommandError> {
+        let class = options.value(CommandOption::Class).map(String::as_str);
+        if class.is_some_and(|class| !self.game_data.class_ids().contains(&class)) {
+            Err(CommandError::UnknownClass)?
+        }
+        let region = options.value(CommandOption::Region).map_or(DEFAULT_REGION, String::as_str);
+        if self.game_data.species_table(region).is_none() {
+            Err(CommandError::UnknownRegion)?
+        }
+        Ok(())
+    }
+
+    fn display_format(options: &CommandOptions)->Result<DisplayFormat, CommandError> {
+        options.value(CommandOption::Format).map_or(Ok(DisplayFormat::default()), |format| DisplayFormat::from_str(format))
+    }
+
+    // Without --seed a seed is drawn at random, so whatever is generated can be generated again
+    fn random_provider(options: &CommandOptions)->Result<SeededRandomProvider, CommandError> {
+        options.value(CommandOption::Seed)
+               .map_or(Ok(rand::random()), |seed| seed.parse().map_err(|_| CommandError::InvalidArguments))
+               .map(SeededRandomProvider::new)
+    }
+
+    // Every NPC of a batch gets its own fork of the provider and is generated on one of the worker threads.
+    // The seed of an NPC with locked sections does not generate it again, so it is neither shown nor saved
+    fn generate_npcs_with<R: RandomProvider + Send>(&self, random_provider: R, options: &CommandOptions, npc_args: &GenerateNpcCommandArgs, arg_offset: Option<usize>,
+        count: usize, print: &mut dyn FnMut(String))->Result<(), CommandError> {
+        let displayer = Self::display_format(options)?.displayer(ConsoleSettings::from_options(options));
+        let reproducible = !options.is_set(CommandOption::Lock);
+        if count == 1 {
+            let seed = random_provider.seed().filter(|_| reproducible);
+            let character = self.generate_npc_with(random_provider, options, npc_args, arg_offset)?;
+            print(displayer.print(&character));
+            if let Some(id) = options.value(CommandOption::Save) {
+                // saving under a taken id replaces the NPC, the tags it was given are kept and --tag adds to them
+                let mut tags = Self::saved_npc(options)?.map_or(vec![], |npc| npc.tags);
+                for tag in options.value(CommandOption::Tag).map_or(vec![], |tags| tags.split(TAG_SEPARATOR).collect()) {
+                    if !tags.iter().any(|own| own == tag) {
+                        tags.push(tag.to_string());
+                    }
+                }
+                let npc = SavedNpc {
+                    id: id.clone(),
+                    tags,
+                    seed,
+                    careers: npc_args.professions(arg_offset).ok_or(CommandError::TooFewArguments(0, 2))?.map(str::to_string).collect(),
+                    species: npc_args.species(arg_offset).ok_or(CommandError::TooFewArguments(1, 2))?.clone(),
+                    options: options.retained(&GENERATION_OPTIONS),
+                    character,
+                };
+                print(format!("Saved to {}", Self::npc_store(options)?.save(&npc)?.display()));
+            }
+            return Ok(());
+        }
+        let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get).min(count);
+        let mut work: Vec<Vec<(usize, R)>> = (0..workers).map(|_| vec![]).collect();
+        for index in 0..count {
+            work[index % workers].push((index, random_provider.fork(index as u64)));
+        }
+
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            for work in work {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    for (index, random_provider) in work {
+                        let seed = random_provider.seed().filter(|_| reproducible);
+                        if sender.send((index, seed, self.generate_npc_with(random_provider, options, npc_args, arg_offset))).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            // NPCs finish out of order, each is printed once all the ones before it are
+            let mut finished = BTreeMap::new();
+            let mut next = 0;
+            for (index, seed, character) in receiver {
+                finished.insert(index, (seed, character));
+                while let Some((seed, character)) = finished.remove(&next) {
+                    print(Self::batch_separator(next, count, seed));
+                    print(displayer.print(&character?));
+                    next += 1;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    // The seed of a forked provider regenerates that NPC alone with --seed
+    fn batch_separator(index: usize, count: usize, seed: Option<u64>)->String {
+        match seed {
+            Some(seed) => format!("--- {}/{} (seed {}) ---", index + 1, count, seed),
+            None => format!("--- {}/{} ---", index + 1, count),
+        }
+    }
+
+    // Random species and careers are rolled with the same provider as the character, so a seed reproduces all of them
+    fn generate_npc_with<R: RandomProvider>(&self, random_provider: R, options: &CommandOptions, npc_args: &GenerateNpcCommandArgs, arg_offset: Option<usize>)
+        ->Result<Character, CommandError> {
+        let species = npc_args.species(arg_offset).ok_or(CommandError::TooFewArguments(1, 2))?;
+        let careers = npc_args.professions(arg_offset).ok_or(CommandError::TooFewArguments(0, 2))?;
+        match options.value(CommandOption::Lock) {
+            Some(locked) => {
+                let locked = CharacterSection::parse_list(locked).map_err(|_| CommandError::InvalidArguments)?;
+                let reroll_provider = random_provider.fork(REROLL_STREAM);
+                let (kept, professions, species) = match Self::saved_npc(options)? {
+                    // rerolled as it was saved, so the careers and species given have to be the saved ones
+                    Some(npc) if !npc.careers.iter().map(String::as_str).eq(careers.clone()) || npc.species != *species => {
+                        Err(CommandError::InvalidArguments)?
+                    }
+                    Some(npc) => {
+                        let (professions, species) = self.resolve_saved_npc(&reroll_provider, &npc)?;
+                        (npc.character, professions, species)
+                    }
+                    None => {
+                        let seed: u64 = options.value(CommandOption::Seed)
+                                               .ok_or(CommandError::MissingSeed)?
+                                               .parse()
+                                               .map_err(|_| CommandError::InvalidArguments)?;
+                        let seeded_provider = SeededRandomProvider::new(seed);
+                        let (professions, species) = self.resolve_npc(&seeded_provider, options, careers, species)?;
+                        (Self::character_generator(seeded_provider, options)?.generate(&professions, &species), professions, species)
+                    }
+                };
+                Ok(Self::character_generator(reroll_provider, options)?.reroll(&kept, &CharacterSection::except(&locked), &professions, &species))
+            }
+            None => self.generate_npc_from(random_provider, options, careers, species),
+        }
+    }
+
+    // The NPC saved under the --save id, if there is one yet
+    fn saved_npc(options: &CommandOptions)->Result<Option<SavedNpc>, CommandError> {
+        let Some(id) = options.value(CommandOption::Save) else { return Ok(None) };
+        match Self::npc_store(options)?.load(id) {
+            Ok(npc) => Ok(Some(npc)),
+            Err(CommandError::UnknownNpc) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn generate_npc_from<'b, R: RandomProvider>(&self, random_provider: R, options: &CommandOptions, careers: impl Iterator<Item=&'b str>, species: &str)
+        ->Result<Character, CommandError> {
+        let (professions, species) = self.resolve_npc(&random_provider, options, careers, species)?;
+        Self::generate_character(random_provider, options, &professions, &species)
+    }
+
+    // Rolls the random species and careers, the character is rolled with the same provider afterwards
+    fn resolve_npc<'b, R: RandomProvider>(&self, random_provider: &R, options: &CommandOptions, careers: impl Iterator<Item=&'b str>, species: &str)
+        ->Result<(Vec<Profession>, Species), CommandError> {
+        let species_id = self.resolve_species(random_provider, options, species)?;
+        let species: Species = self.game_data.get_by_id(&species_id).ok_or(CommandError::UnknownSpecies)?;
+        let class = options.value(CommandOption::Class).map(String::as_str);
+        let careers = careers.map(|profession| match GenerateNpcCommandArgs::random_career_level(profession) {
+                                  Some(level) => self.roll_career(random_provider, &species_id, class).map(|career| format!("{}_{}", career, level)),
+                                  None => Ok(profession.to_string()),
+                              })
+                              .collect::<Result<Vec<String>, CommandError>>()?;
+        let professions = GenerateNpcCommandArgs::profession_ids(careers.iter().map(String::as_str))
+                              .iter()
+                              .map(|id| self.game_data.get_by_id(id).ok_or(CommandError::UnknownProfession))
+                              .collect::<Result<Vec<Profession>, CommandError>>()?;
+        Ok((professions, species))
+    }
+
+    fn encounter(&self, args: &Vec<String>)->Result<(), CommandError> {
+        println!("{}", self.encounter_sheet(args)?);
+        Ok(())
+    }
+
+    fn encounter_sheet(&self, args: &Vec<String>)->Result<String, CommandError> {
+        let options = CommandOptions(args);
+        let encounter: Encounter = options.positional()
+                                          .get(1)
+                                          .and_then(|encounter| self.game_data.get_by_id(encounter))
+                                          .ok_or(CommandError::UnknownEncounter)?;
+        self.validate_tables(&options)?;
+        let format = Self::display_format(&options)?;
+
+        let members = self.generate_encounter_with(Self::random_provider(&options)?, &options, &encounter)?;
+        let displayer = format.displayer(ConsoleSettings::from_options(&options));
+        Ok(EncounterDisplayer::new(displayer.as_ref()).print(&encounter, &members))
+    }
+
+    // Chances and counts are rolled on the encounter's provider, every member is generated with a fork of it
+    fn generate_encounter_with<R: RandomProvider>(&self, random_provider: R, options: &CommandOptions, encounter: &Encounter)
+        ->Result<Vec<GroupMember>, CommandError> {
+        let percentile = GenerationBase { base_value: 0, dices: vec![100] };
+        let mut members: Vec<GroupMember> = vec![];
+        for member in &encounter.members {
+            if random_provider.generate(&percentile) > member.chance {
+                continue;
+            }
+            for _ in 0..random_provider.generate(&member.count) {
+                let species = member.species.as_ref().unwrap_or(&encounter.species);
+                let character = self.generate_npc_from(random_provider.fork(members.len() as u64), options, member.careers.split(' '), species)?;
+                members.push(GroupMember { role: member.role.clone(), character: Character { affiliation: encounter.affiliation.clone(), ..character } });
+            }
+        }
+        if encounter.shared_surname {
+            if let Some(surname) = members.first().and_then(GroupMember::surname).map(str::to_string) {
+                members.iter_mut().skip(1).for_each(|member| member.take_surname(&surname));
+            }
+        }
+        Ok(members)
+    }
+
+    fn assess(&self, args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let encounter: Encounter = options.positional()
+                                          .get(1)
+                                          .and_then(|encounter| self.game_data.get_by_id(encounter))
+                                          .ok_or(CommandError::UnknownEncounter)?;
+        let party = GenerateNpcCommandArgs(args).professions(Some(2)).ok_or(CommandError::TooFewArguments(1, 2))?;
+        self.validate_tables(&options)?;
+
+        let assessment = self.assess_with(Self::random_provider(&options)?, &options, &encounter, party)?;
+        println!("{}", AssessmentDisplayer::with_settings(ConsoleSettings::from_options(&options)).print(&encounter.name, &assessment));
+        Ok(())
+    }
+
+    // Every PC of the party is generated like an NPC with a single career
+    fn assess_with<'b, R: RandomProvider>(&self, random_provider: R, options: &CommandOptions, encounter: &Encounter, party: impl Iterator<Item=&'b str>)
+        ->Result<Assessment, CommandError> {
+        let group: Vec<Character> = self.generate_encounter_with(random_provider.fork(0), options, encounter)?
+                                        .into_iter()
+                                        .map(|member| member.character)
+                                        .collect();
+        let party_provider = random_provider.fork(1);
+        let party = party.enumerate()
+                         .map(|(index, career)| self.generate_npc_from(party_provider.fork(index as u64), options, std::iter::once(career), PARTY_SPECIES))
+                         .collect::<Result<Vec<Character>, CommandError>>()?;
+        Ok(Assessment::new(&party, &group))
+    }
+
+    // The seed rolls random careers and species as they were, without one they are rolled anew for later rerolls
+    fn resolve_saved_npc<R: RandomProvider>(&self, random_provider: &R, npc: &SavedNpc)->Result<(Vec<Profession>, Species), CommandError> {
+        let saved_options = CommandOptions(&npc.options);
+        let careers = npc.careers.iter().map(String::as_str);
+        match npc.seed {
+            Some(seed) => self.resolve_npc(&SeededRandomProvider::new(seed), &saved_options, careers, &npc.species),
+            None => self.resolve_npc(random_provider, &saved_options, careers, &npc.species),
+        }
+    }
+
+    fn resolve_species<R: RandomProvider>(&self, random_provider: &R, options: &CommandOptions, species: &str)->Result<String, CommandError> {
+        match species {
+            RANDOM_SPECIES => self.roll_species(random_provider, options.value(CommandOption::Region).map_or(DEFAULT_REGION, String::as_str)),
+            species_id => Ok(species_id.to_string()),
+        }
+    }
+
+    fn roll_species<R: RandomProvider>(&self, random_provider: &R, region: &str)->Result<String, CommandError> {
+        let table = self.game_data.species_table(region).ok_or(CommandError::UnknownRegion)?;
+        table.lookup(random_provider.generate(&table.dice()))
+             .map(str::to_string)
+             .ok_or(CommandError::UnknownRegion)
+    }
+
+    // NPCs go to the campaign database when one is given, to the library otherwise
+    fn npc_store(options: &CommandOptions)->Result<Box<dyn NpcStore>, CommandError> {
+        match options.is_set(CommandOption::Campaign) {
+            true => Ok(Box::new(Self::campaign(options)?)),
+            false => options.value(CommandOption::Library)
+                            .map(PathBuf::from)
+                            .or_else(NpcLibrary::default_directory)
+                            .map(|directory| Box::new(NpcLibrary::new(directory)) as Box<dyn NpcStore>)
+                            .ok_or(CommandError::IoFailure),
+        }
+    }
+
+    fn campaign(options: &CommandOptions)->Result<Campaign, CommandError> {
+        options.value(CommandOption::Campaign)
+               .map(PathBuf::from)
+               .or_else(Campaign::default_path)
+               .ok_or(CommandError::IoFailure)
+               .and_then(Campaign::open)
+    }
+
+    fn list(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let species = options.value(CommandOption::Species).map(String::as_str);
+        let career = options.value(CommandOption::Career).map(String::as_str);
+        let tag = options.value(CommandOption::Tag).map(String::as_str);
+        let npcs: Vec<SavedNpc> = Self::npc_store(&options)?.list()?
+                                                            .into_iter()
+                                                            .filter(|npc| npc.matches(species, career, tag))
+                                                            .collect();
+        match npcs.is_empty() {
+            true => println!("No NPCs saved"),
+            false => npcs.iter().for_each(|npc| println!("{}", Self::library_entry(npc))),
+        }
+        Ok(())
+    }
+
+    // "gunther: Gunther Hahn, Human, Soldier 2 [altdorf, bodyguard]"
+    fn library_entry(npc: &SavedNpc)->String {
+        let summary = [npc.character.name.as_str(), npc.character.species.as_str(), &npc.character.formatted_career_history()]
+                          .into_iter()
+                          .filter(|part| !part.is_empty())
+                          .collect::<Vec<&str>>()
+                          .join(", ");
+        match npc.tags.is_empty() {
+            true => format!("{}: {}", npc.id, summary),
+            false => format!("{}: {} [{}]", npc.id, summary, npc.tags.join(", ")),
+        }
+    }
+
+    fn show(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let displayer = Self::display_format(&options)?.displayer(ConsoleSettings::from_options(&options));
+        let id = options.positional()[1];
+        match options.is_set(CommandOption::Campaign) {
+            true => {
+                let npc = Self::campaign(&options)?.get(id)?;
+                println!("{}", displayer.print(&npc.npc.character));
+                print!("{}", npc.formatted_campaign_details());
+            }
+            false => println!("{}", displayer.print(&Self::npc_store(&options)?.load(id)?.character)),
+        }
+        Ok(())
+    }
+
+    fn delete(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let id = options.positional()[1];
+        Self::npc_store(&options)?.delete(id)?;
+        println!("Deleted {}", id);
+        Ok(())
+    }
+
+    fn search(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let npcs = Self::campaign(&options)?.search(options.positional()[2])?;
+        match npcs.is_empty() {
+            true => println!("No NPCs found"),
+            false => npcs.iter().for_each(|npc| println!("{}", Self::library_entry(&npc.npc))),
+        }
+        Ok(())
+    }
+
+    fn tag(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let (id, tags) = (options.positional()[2], options.positional()[3]);
+        let tags: Vec<String> = tags.split(TAG_SEPARATOR).map(str::to_string).collect();
+        let campaign = Self::campaign(&options)?;
+        match options.is_set(CommandOption::Remove) {
+            true => campaign.untag(id, &tags)?,
+            false => campaign.tag(id, &tags)?,
+        }
+        println!("{}", Self::library_entry(&campaign.load(id)?));
+        Ok(())
+    }
+
+    fn annotate(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let id = options.positional()[2];
+        let campaign = Self::campaign(&options)?;
+        campaign.annotate(id, options.value(CommandOption::Location).map(String::as_str), options.value(CommandOption::Faction).map(String::as_str),
+                          options.value(CommandOption::Notes).map(String::as_str))?;
+        print!("{}", campaign.get(id)?.formatted_campaign_details());
+        Ok(())
+    }
+
+    fn relate(args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        let (id, other, relationship) = (options.positional()[2], options.positional()[3], options.positional()[4]);
+        Self::campaign(&options)?.relate(id, other, relationship)?;
+        println!("{} is {} of {}", id, relationship, other);
+        Ok(())
+    }
+
+    // Lines are read until "quit" or the end of input, a failed line is reported and the session goes on
+    fn repl(&self, args: &Vec<String>)->Result<(), CommandError> {
+        let options = CommandOptions(args);
+        self.validate_tables(&options)?;
+        let displayer = Self::display_format(&options)?.displayer(ConsoleSettings::from_options(&options));
+
+        let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new().map_err(|_| CommandError::IoFailure)?;
+        editor.set_helper(Some(ReplHelper::new(&self.game_data)));
+        let history = Self::history_file();
+        if let Some(history) = &history {
+            // there is none before the first session
+            editor.load_history(history).ok();
+        }
+
+        let mut session = ReplSession::default();
+        loop {
+            let line = match editor.readline(REPL_PROMPT) {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(_) => break,
+            };
+            editor.add_history_entry(line.as_str()).ok();
+            match ReplCommand::parse(&line) {
+                Ok(Some(ReplCommand::Quit)) => break,
+                Ok(Some(command)) => if let Err(e) = self.execute_repl(&mut session, &options, command, displayer.as_ref()) {
+                    self.print_error(e);
+                },
+                Ok(None) => {}
+                Err(CommandError::InvalidCommand) => {
+                    println!("Command \"{}\" is not recognized, \"help\" lists the commands", line.split_whitespace().next().unwrap_or_default());
+                }
+                Err(e) => self.print_error(e),
+            }
+        }
+        match &history {
+            Some(history) => editor.save_history(history).map_err(|_| CommandError::IoFailure),
+            None => Ok(()),
+        }
+    }
+
+    // Changes to the NPC are printed right away
+    fn execute_repl(&self, session: &mut ReplSession, options: &CommandOptions, command: ReplCommand, displayer: &dyn CharacterDisplayer<String>)
+        ->Result<(), CommandError> {
+        match command {
+            ReplCommand::Generate { careers, species } => {
+                session.careers = careers;
+                session.species = species;
+                self.roll_session(session, options)?;
+            }
+            ReplCommand::Reroll(sections) if sections.is_empty() && session.locked.is_empty() => {
+                session.character.as_ref().ok_or(CommandError::NoCharacter)?;
+                self.roll_session(session, options)?;
+            }
+            ReplCommand::Reroll(sections) => {
+                let sections = if sections.is_empty() { CharacterSection::except(&session.locked) } else { sections };
+                let (Some(character), Some(species)) = (&session.character, &session.rolled_species) else { Err(CommandError::NoCharacter)? };
+                let character = Self::character_generator(ThreadBasedRandomProvider{}, options)?.reroll(character, &sections, &session.professions, species);
+                session.character = Some(character);
+                session.seed = None;
+            }
+            ReplCommand::RerollCharacteristic(attribute) => {
+                let (Some(character), Some(species)) = (&mut session.character, &session.rolled_species) else { Err(CommandError::NoCharacter)? };
+                RandomCharacterGenerator::new(ThreadBasedRandomProvider{}).reroll_characteristic(character, attribute, species);
+                session.seed = None;
+            }
+            ReplCommand::Lock(sections) => {
+                for section in sections {
+                    if !session.locked.contains(&section) {
+                        session.locked.push(section);
+                    }
+                }
+                println!("{}", session.formatted_locked());
+                return Ok(());
+            }
+            ReplCommand::Unlock(sections) => {
+                session.locked.retain(|section| !sections.is_empty() && !sections.contains(section));
+                println!("{}", session.formatted_locked());
+                return Ok(());
+            }
+            ReplCommand::AddTalent(name) => {
+                let character = session.character.as_mut().ok_or(CommandError::NoCharacter)?;
+                let talent: Talent = self.game_data.get_by_id(&name).ok_or(CommandError::UnknownTalent)?;
+                RandomCharacterGenerator::new(ThreadBasedRandomProvider{}).learn_talent(character, &talent);
+                session.seed = None;
+            }
+            ReplCommand::AddSkill(name, advances) => {
+                let character = session.character.as_mut().ok_or(CommandError::NoCharacter)?;
+                let skill: SkillDefinition = self.game_data.get_by_id(&name).ok_or(CommandError::UnknownSkill)?;
+                RandomCharacterGenerator::new(ThreadBasedRandomProvider{}).learn_skill(character, &skill, advances);
+                session.seed = None;
+            }
+            ReplCommand::Show => {}
+            ReplCommand::Save(id) => {
+                let character = session.character.clone().ok_or(CommandError::NoCharacter)?;
+                let npc = SavedNpc { id, tags: vec![], seed: session.seed, careers: session.careers.clone(), species: session.species.clone(),
+                                     options: options.retained(&GENERATION_OPTIONS), character };
+                println!("Saved to {}", Self::npc_store(options)?.save(&npc)?.display());
+                return Ok(());
+            }
+            ReplCommand::Load(id) => {
+                let npc = Self::npc_store(options)?.load(&id)?;
+                let (professions, species) = self.resolve_saved_npc(&ThreadBasedRandomProvider{}, &npc)?;
+                *session = ReplSession { character: Some(npc.character), careers: npc.careers, species: npc.species, professions,
+                                         rolled_species: Some(species), locked: std::mem::take(&mut session.locked), seed: npc.seed };
+            }
+            ReplCommand::Help => {
+                print!("{}", ReplCommand::help());
+                return Ok(());
+            }
+            ReplCommand::Quit => return Ok(()),
+        }
+        println!("{}", displayer.print(session.character.as_ref().ok_or(CommandError::NoCharacter)?));
+        Ok(())
+    }
+
+    // Every roll of a session gets a fresh seed, so an NPC worth keeping can be generated again with generate-npc --seed
+    fn roll_session(&self, session: &mut ReplSession, options: &CommandOptions)->Result<(), CommandError> {
+        let seed: u64 = rand::random();
+        let random_provider = SeededRandomProvider::new(seed);
+        let (professions, species) = self.resolve_npc(&random_provider, options, session.careers.iter().map(String::as_str), &session.species)?;
+        let character = Self::generate_character(random_provider, options, &professions, &species)?;
+        println!("(seed {})", seed);
+        session.seed = Some(seed);
+        session.professions = professions;
+        session.rolled_species = Some(species);
+        session.character = Some(character);
+        Ok(())
+    }
+
+    fn history_file()->Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+    }
+
+    fn roll_career<R: RandomProvider>(&self, random_provider: &R, species_id: &str, class: Option<&str>)->Result<String, CommandError> {
+        let table = self.game_data.career_table(species_id, class);
+        table.lookup(random_provider.generate(&table.dice()))
+             .map(str::to_string)
+             .ok_or(CommandError::NoCareerAvailable)
+    }
+
+    fn generate_character<R: RandomProvider>(random_provider: R, options: &CommandOptions, professions: &[Profession], species: &Species)
+        ->Result<Character, CommandError> {
+        Ok(Self::character_generator(random_provider, options)?.generate(professions, species))
+    }
+
+    fn character_generator<R: RandomProvider>(random_provider: R, options: &CommandOptions)->Result<RandomCharacterGenerator<R>, CommandError> {
+        let mut generator = RandomCharacterGenerator::new(random_provider);
+        if options.is_set(CommandOption::Income) {
+            generator = generator.with_income();
+        }
+        if let Some(name) = options.value(CommandOption::Name) {
+            generator = generator.with_name(name);
+        }
+        if let Some(gender) = options.value(CommandOption::Gender) {
+            generator = generator.with_gender(gender.parse().map_err(|_| CommandError::InvalidArguments)?);
+        }
+        if options.is_set(CommandOption::Details) {
+            generator = generator.with_details();
+        }
+        if options.is_set(CommandOption::WithPersonality) {
+            generator = generator.with_personality();
+        }
+        if options.is_set(CommandOption::Prune) {
+            generator = generator.with_pruning();
+        }
+        if options.is_set(CommandOption::Pack) {
+            generator = generator.with_packing();
+        }
+        Ok(generator)
+    }
+
+    fn generate_creature(&self, args: &Vec<String>)->Result<(), CommandError> {
+        println!("{}", self.creature_sheet(args)?);
+        Ok(())
+    }
+
+    fn creature_sheet(&self, args: &Vec<String>)->Result<String, CommandError> {
+        let options = CommandOptions(args);
+        let creature: Creature = options.positional()
+                                        .get(1)
+                                        .and_then(|creature| self.game_data.get_by_id(creature))
+                                        .ok_or(CommandError::UnknownCreature)?;
+        let format = Self::display_format(&options)?;
+
+        let character = Self::generate_creature_with(Self::random_provider(&options)?, &options, &creature);
+        Ok(format.displayer(ConsoleSettings::from_options(&options)).print(&character))
+    }
+
+    fn generate_creature_with<R: RandomProvider>(random_provider: R, options: &CommandOptions, creature: &Creature)->Character {
+        let mut generator = RandomCharacterGenerator::new(random_provider);
+        if let Some(name) = options.value(CommandOption::Name) {
+            generator = generator.with_name(name);
+        }
+        generator.generate_creature(creature)
+    }
+
     fn print_usage_message() {
         println!("{}", USAGE_MESSAGE);
     }
@@ -46,7 +723,11 @@ impl App {
     }
 
     fn get_formatted_command_list()->String {
-        Command::iterator().map(|cmd| format!("\t-{}\n", cmd.command_description())).collect()
+        Command::iterator().flat_map(|cmd| match cmd.subcommands() {
+                               [] => std::slice::from_ref(cmd),
+                               subcommands => subcommands,
+                           })
+                           .map(|cmd| format!("\t-{}\n", cmd.command_description())).collect()
     }
 }
 #[cfg(test)]
@@ -75,6 +756,352 @@ mod test {
         assert_eq!(App::new().run(invalid_command.into_iter()).unwrap_err(), CommandError::TooFewArguments(1, 2))
     }
 
+    #[test] 
+    fn app_unknown_profession() {
+        let invalid_command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "astronaut_1".into(), "human".into()];
+        assert_eq!(App::new().run(invalid_command.into_iter()).unwrap_err(), CommandError::UnknownProfession)
+    }
+
+    #[test] 
+    fn app_unknown_species() {
+        let invalid_command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "soldier_1".into(), "skaven".into()];
+        assert_eq!(App::new().run(invalid_command.into_iter()).unwrap_err(), CommandError::UnknownSpecies)
+    }
+
+    #[test] 
+    fn app_generate_npc() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "soldier_2 outlaw_1".into(), "dwarf".into(), "--format".into(), "compact".into(), "--income".into(),
+                                    "--details".into(), "--seed".into(), "7".into(), "--with-personality".into(), "--prune".into(), "--pack".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()))
+    }
+
+    #[test] 
+    fn app_generate_npc_of_the_last_career_level() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "soldier_4".into(), "human".into(), "--seed".into(), "11".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()))
+    }
+
+    #[test] 
+    fn app_generate_npc_with_random_career() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "random_2 soldier_1".into(), "norscan".into(), "--class".into(), "rogues".into(), "--seed".into(), "3".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()))
+    }
+
+    #[test] 
+    fn app_random_career_of_unavailable_class() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "random".into(), "ogre".into(), "--class".into(), "academics".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::NoCareerAvailable);
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "random".into(), "ogre".into(), "--class".into(), "pirates".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::UnknownClass)
+    }
+
+    #[test] 
+    fn app_generate_npc_of_random_species() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "random_2".into(), "random".into(), "--region".into(), "tilea".into(), "--seed".into(), "11".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()));
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "soldier_1".into(), "high_elf".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()))
+    }
+
+    #[test] 
+    fn app_unknown_region() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "soldier_1".into(), "random".into(), "--region".into(), "lustria".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::UnknownRegion)
+    }
+
+    #[test] 
+    fn app_generate_many_npcs() {
+        let app = App::new();
+        let generate = |args: &[&str]| {
+            let mut printed = vec![];
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            app.generate_npc_to(&args, &mut |text| printed.push(text)).map(|_| printed)
+        };
+        let printed = generate(&["generate-npc", "random", "random", "--count", "5", "--seed", "2"]).unwrap();
+        assert_eq!(printed.len(), 10);
+        assert_eq!(generate(&["generate-npc", "random", "random", "--count", "5", "--seed", "2"]).unwrap(), printed);
+        // the seed in each separator generates that NPC alone
+        for (index, npc) in printed.chunks(2).enumerate() {
+            let seed = npc[0].trim_end_matches(") ---").rsplit(' ').next().unwrap();
+            assert!(npc[0].starts_with(&format!("--- {}/5 (seed ", index + 1)));
+            assert_eq!(generate(&["generate-npc", "random", "random", "--seed", seed]).unwrap(), vec![npc[1].clone()]);
+        }
+
+        let batch: Vec<String> = ["generate-batch", "20", "soldier_2", "human", "--format", "compact"].into_iter().map(String::from).collect();
+        let mut printed = vec![];
+        app.generate_npc_to(&Command::batch_as_generate_npc(&batch), &mut |text| printed.push(text)).unwrap();
+        assert_eq!(printed.len(), 40);
+        assert!(printed.iter().skip(1).step_by(2).all(|sheet| sheet.contains(", Human Soldier 2 ")));
+    }
+
+    #[test] 
+    fn app_batch_stops_at_unknown_profession() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-batch".into(), "8".into(), "astronaut_1".into(), "human".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::UnknownProfession)
+    }
+
+    #[test] 
+    fn batch_separator_shows_the_seed() {
+        assert_eq!(App::batch_separator(2, 10, Some(77)), "--- 3/10 (seed 77) ---");
+        assert_eq!(App::batch_separator(0, 10, None), "--- 1/10 ---");
+    }
+
+    #[test] 
+    fn app_encounter() {
+        let app = App::new();
+        let args: Vec<String> = vec!["encounter".into(), "bandit_gang".into(), "--seed".into(), "4".into(), "--format".into(), "compact".into()];
+        let sheet = app.encounter_sheet(&args).unwrap();
+        assert_eq!(app.encounter_sheet(&args).unwrap(), sheet);
+        let sections: Vec<&str> = sheet.split("\n\n").collect();
+        assert_eq!(sections[0], "=== Bandit Gang — Reikwald Bandits ===");
+        let roster: Vec<&str> = sections[1].lines().collect();
+        let members = roster.len() - 1;
+        assert_eq!(roster[0], format!("Roster ({}):", members));
+        assert!(roster[1].starts_with("  Leader: ") && roster[1].contains(", Outlaw 3 (Outlaw Chief), W "));
+        assert!(roster[2..].iter().all(|line| line.contains(", Outlaw 1 (Brigand), W ") || line.contains(", Witch 1 (Hexer), W ")));
+        assert!((3..=10).contains(&members));
+        assert_eq!(sections.len(), 2 + members);
+        assert!(sections[2..].iter().enumerate().all(|(index, section)| section.starts_with(&format!("--- {}/{} ---\n", index + 1, members))));
+
+        let command:Vec<String> =vec!["app_name".into(), "encounter".into(), "bandit_gang".into(), "--seed".into(), "4".into(), "--format".into(), "compact".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()));
+        let command:Vec<String> =vec!["app_name".into(), "encounter".into(), "lone_troll".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::UnknownEncounter)
+    }
+
+    #[test] 
+    fn encounter_rolls_its_members() {
+        let app = App::new();
+        let args: Vec<String> = vec![];
+        let encounter: Encounter = app.game_data.get_by_id("dwarf_caravan").unwrap();
+        let members = app.generate_encounter_with(SeededRandomProvider::new(21), &CommandOptions(&args), &encounter).unwrap();
+        assert_eq!(members[0].role.as_deref(), Some("Merchant"));
+        assert!((3..=11).contains(&members.len()));
+        let surname = members[0].surname().unwrap();
+        assert!(members.iter().all(|member| member.surname() == Some(surname)));
+
+        let encounter: Encounter = app.game_data.get_by_id("bandit_gang").unwrap();
+        let members = app.generate_encounter_with(SeededRandomProvider::new(21), &CommandOptions(&args), &encounter).unwrap();
+        assert!(members.iter().all(|member| member.character.affiliation.as_deref() == Some("Reikwald Bandits")));
+        assert_eq!(members[0].character.formatted_career_history(), "Outlaw 3 (Outlaw Chief)");
+    }
+
+    #[test] 
+    fn app_assess() {
+        let command:Vec<String> =vec!["app_name".into(), "assess".into(), "militia_patrol".into(), "soldier_2 wizard_1 thief_2 priest_1".into(), "--seed".into(), "8".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()));
+        let command:Vec<String> =vec!["app_name".into(), "assess".into(), "militia_patrol".into(), "astronaut_1".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::UnknownProfession)
+    }
+
+    #[test] 
+    fn assessment_grows_with_the_party() {
+        let app = App::new();
+        let args: Vec<String> = vec![];
+        let encounter: Encounter = app.game_data.get_by_id("bandit_gang").unwrap();
+        let assess = |party: &str| app.assess_with(SeededRandomProvider::new(5), &CommandOptions(&args), &encounter, party.split(' ')).unwrap();
+        let alone = assess("villager_1");
+        let company = assess("soldier_3 soldier_3 soldier_3 soldier_3 soldier_3 soldier_3");
+        assert_eq!((alone.party.members, company.party.members), (1, 6));
+        assert_eq!(alone.group, company.group);
+        assert!(company.difficulty < alone.difficulty);
+    }
+
+    #[test] 
+    fn repl_session_works_on_one_npc() {
+        let app = App::new();
+        let args: Vec<String> = vec![];
+        let options = CommandOptions(&args);
+        let displayer = DisplayFormat::Compact.displayer(ConsoleSettings::default());
+        let mut session = ReplSession::default();
+        let mut execute = |line: &str| app.execute_repl(&mut session, &options, ReplCommand::parse(line).unwrap().unwrap(), displayer.as_ref());
+        assert_eq!(execute("reroll"), Err(CommandError::NoCharacter));
+        assert_eq!(execute("gen soldier_2 random"), Ok(()));
+        assert_eq!(execute("reroll strength"), Ok(()));
+        assert_eq!(execute("add-talent Very Strong"), Ok(()));
+        assert_eq!(execute("add-talent Very Clever"), Err(CommandError::UnknownTalent));
+        assert_eq!(execute("add-skill Lore (Reikland) 3"), Ok(()));
+        assert_eq!(execute("reroll"), Ok(()));
+        assert_eq!(execute("show"), Ok(()));
+        assert_eq!(session.character.as_ref().unwrap().formatted_career_history(), "Soldier 2");
+        assert!(session.rolled_species.is_some());
+        assert_eq!(session.species, "random");
+    }
+
+    #[test] 
+    fn repl_reroll_keeps_locked_sections() {
+        let app = App::new();
+        let args: Vec<String> = vec![];
+        let options = CommandOptions(&args);
+        let displayer = DisplayFormat::Compact.displayer(ConsoleSettings::default());
+        let mut session = ReplSession::default();
+        let execute = |session: &mut ReplSession, line: &str| app.execute_repl(session, &options, ReplCommand::parse(line).unwrap().unwrap(),
+                                                                                   displayer.as_ref());
+        assert_eq!(execute(&mut session, "gen soldier_2 dwarf"), Ok(()));
+        let kept = session.character.clone().unwrap();
+        assert_eq!(execute(&mut session, "lock characteristics name trappings skills"), Ok(()));
+        assert_eq!(execute(&mut session, "reroll"), Ok(()));
+        let rerolled = session.character.clone().unwrap();
+        assert_eq!((&rerolled.name, rerolled.strength, rerolled.skills.len()), (&kept.name, kept.strength, kept.skills.len()));
+
+        assert_eq!(execute(&mut session, "unlock name"), Ok(()));
+        assert_eq!(session.locked, vec![CharacterSection::Characteristics, CharacterSection::Trappings, CharacterSection::Skills]);
+        assert_eq!(execute(&mut session, "unlock"), Ok(()));
+        assert!(session.locked.is_empty());
+    }
+
+    #[test] 
+    fn app_generate_npc_with_locked_sections() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "random_2".into(), "random".into(), "--seed".into(), "9".into(),
+                                    "--lock".into(), "characteristics,name".into(), "--count".into(), "3".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()));
+        let command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "soldier_1".into(), "human".into(), "--lock".into(), "name".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::MissingSeed);
+    }
+
+    #[test] 
+    fn locked_sections_come_from_the_seeded_npc() {
+        let app = App::new();
+        let seeded: Vec<String> = vec!["generate-npc".into(), "soldier_2".into(), "random".into(), "--seed".into(), "9".into()];
+        let locked: Vec<String> = seeded.iter().cloned().chain(["--lock".into(), "characteristics,name".into()]).collect();
+        let kept = app.generate_npc_with(SeededRandomProvider::new(9), &CommandOptions(&seeded), &GenerateNpcCommandArgs(&seeded), Some(1)).unwrap();
+        let reroll = || app.generate_npc_with(SeededRandomProvider::new(9), &CommandOptions(&locked), &GenerateNpcCommandArgs(&locked), Some(1)).unwrap();
+        let rerolled = reroll();
+        assert_eq!((&rerolled.name, rerolled.weapon_skill, rerolled.fellowship), (&kept.name, kept.weapon_skill, kept.fellowship));
+        // the unlocked sections are rolled with a fork of the seed, so they come out the same every time
+        assert_eq!(serde_json::to_string(&reroll()).unwrap(), serde_json::to_string(&rerolled).unwrap());
+    }
+
+    fn library_dir(name: &str)->String {
+        let directory = std::env::temp_dir().join(format!("wfrp-npc-app-{}-{}", name, std::process::id()));
+        std::fs::remove_dir_all(&directory).ok();
+        directory.to_string_lossy().into_owned()
+    }
+
+    #[test] 
+    fn app_saves_lists_shows_and_deletes_npcs() {
+        let library = library_dir("commands");
+        let run = |args: &[&str]| App::new().run(std::iter::once("app_name").chain(args.iter().copied()).map(String::from));
+        assert_eq!(run(&["generate-npc", "random_2", "dwarf", "--details", "--save", "grimnir", "--tag", "altdorf,bodyguard", "--library", &library]), Ok(()));
+        assert_eq!(run(&["generate-npc", "soldier_1", "human", "--count", "2", "--save", "gunther", "--library", &library]).unwrap_err(),
+                   CommandError::InvalidArguments);
+        assert_eq!(run(&["list", "--species", "dwarf", "--tag", "bodyguard", "--library", &library]), Ok(()));
+        assert_eq!(run(&["show", "grimnir", "--format", "compact", "--library", &library]), Ok(()));
+        assert_eq!(run(&["delete", "grimnir", "--library", &library]), Ok(()));
+        assert_eq!(run(&["show", "grimnir", "--library", &library]).unwrap_err(), CommandError::UnknownNpc);
+        std::fs::remove_dir_all(&library).ok();
+    }
+
+    #[test] 
+    fn locked_sections_come_from_the_saved_npc() {
+        let library = library_dir("lock");
+        let run = |args: &[&str]| App::new().run(std::iter::once("app_name").chain(args.iter().copied()).map(String::from));
+        assert_eq!(run(&["generate-npc", "soldier_2", "dwarf", "--save", "brokk", "--tag", "bodyguard,altdorf", "--library", &library]), Ok(()));
+        let saved = NpcLibrary::new(PathBuf::from(&library)).load("brokk").unwrap();
+        assert_eq!(run(&["generate-npc", "soldier_2", "dwarf", "--lock", "characteristics,name", "--save", "brokk", "--tag", "altdorf,veteran", "--library", &library]), Ok(()));
+        let rerolled = NpcLibrary::new(PathBuf::from(&library)).load("brokk").unwrap();
+        assert_eq!((&rerolled.character.name, rerolled.character.weapon_skill), (&saved.character.name, saved.character.weapon_skill));
+        assert_eq!(rerolled.seed, None);
+        assert_eq!(rerolled.tags, vec!["bodyguard", "altdorf", "veteran"]);
+        assert_eq!(run(&["generate-npc", "outlaw_1", "dwarf", "--lock", "name", "--save", "brokk", "--library", &library]).unwrap_err(),
+                   CommandError::InvalidArguments);
+        std::fs::remove_dir_all(&library).ok();
+    }
+
+    #[test] 
+    fn saved_seed_generates_the_npc_again() {
+        let library = library_dir("seed");
+        let args: Vec<String> = ["app_name", "generate-npc", "random_2", "random", "--region", "tilea", "--save", "ilsa", "--library", &library]
+                                    .into_iter().map(String::from).collect();
+        assert_eq!(App::new().run(args.into_iter()), Ok(()));
+        let npc = NpcLibrary::new(PathBuf::from(&library)).load("ilsa").unwrap();
+        assert_eq!((npc.careers.as_slice(), npc.species.as_str(), npc.options.as_slice()), (&["random_2".to_string()][..], "random", &["--region".to_string(), "tilea".to_string()][..]));
+
+        let app = App::new();
+        let args: Vec<String> = vec!["generate-npc".into(), "random_2".into(), "random".into()];
+        let options: Vec<String> = npc.options.clone();
+        let again = app.generate_npc_with(SeededRandomProvider::new(npc.seed.unwrap()), &CommandOptions(&options), &GenerateNpcCommandArgs(&args), Some(1)).unwrap();
+        assert_eq!(serde_json::to_string(&again).unwrap(), serde_json::to_string(&npc.character).unwrap());
+        std::fs::remove_dir_all(&library).ok();
+    }
+
+    #[test] 
+    fn app_keeps_a_campaign() {
+        let campaign = std::env::temp_dir().join(format!("wfrp-npc-app-campaign-{}.db", std::process::id()));
+        std::fs::remove_file(&campaign).ok();
+        let campaign = campaign.to_string_lossy().into_owned();
+        let run = |args: &[&str]| App::new().run(std::iter::once("app_name").chain(args.iter().copied()).map(String::from));
+        assert_eq!(run(&["generate-npc", "witch_hunter_2", "human", "--save", "ilsa", "--tag", "inquisition", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["generate-npc", "soldier_1", "dwarf", "--save", "brokk", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["npc", "annotate", "ilsa", "--location", "Altdorf", "--faction", "Order of the Silver Hammer", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["npc", "tag", "brokk", "bodyguard,altdorf", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["npc", "tag", "brokk", "bodyguard", "--remove", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["generate-npc", "soldier_1", "dwarf", "--lock", "name", "--save", "brokk", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["npc", "relate", "brokk", "ilsa", "bodyguard", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["npc", "relate", "brokk", "gunther", "rival", "--campaign", &campaign]).unwrap_err(), CommandError::UnknownNpc);
+        assert_eq!(run(&["npc", "search", "witch hunter altdorf", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["show", "brokk", "--format", "compact", "--campaign", &campaign]), Ok(()));
+        assert_eq!(run(&["list", "--tag", "altdorf", "--campaign", &campaign]), Ok(()));
+
+        let args: Vec<String> = vec!["--campaign".into(), campaign.clone()];
+        let found = App::campaign(&CommandOptions(&args)).unwrap().search("witch hunter altdorf").unwrap();
+        assert_eq!(found.iter().map(|npc| npc.npc.id.as_str()).collect::<Vec<&str>>(), vec!["ilsa"]);
+        assert_eq!(App::campaign(&CommandOptions(&args)).unwrap().get("brokk").unwrap().npc.tags, vec!["altdorf"]);
+        std::fs::remove_file(&campaign).ok();
+    }
+
+    #[test] 
+    fn library_entry_sums_up_the_npc() {
+        let character = Character { name: "Gunther Hahn".into(), species: "Human".into(), ..Character::default() };
+        let npc = SavedNpc { id: "gunther".into(), tags: vec!["altdorf".into()], seed: None, careers: vec![], species: "human".into(), options: vec![], character };
+        assert_eq!(App::library_entry(&npc), "gunther: Gunther Hahn, Human [altdorf]");
+    }
+
+    #[test] 
+    fn repl_saves_and_loads_npcs() {
+        let app = App::new();
+        let library = library_dir("repl");
+        let args: Vec<String> = vec!["--library".into(), library.clone()];
+        let options = CommandOptions(&args);
+        let displayer = DisplayFormat::Compact.displayer(ConsoleSettings::default());
+        let execute = |session: &mut ReplSession, line: &str| app.execute_repl(session, &options, ReplCommand::parse(line).unwrap().unwrap(), displayer.as_ref());
+        let mut session = ReplSession::default();
+        assert_eq!(execute(&mut session, "save grimnir"), Err(CommandError::NoCharacter));
+        assert_eq!(execute(&mut session, "gen random_2 dwarf"), Ok(()));
+        assert!(session.seed.is_some());
+        assert_eq!(execute(&mut session, "add-talent Very Strong"), Ok(()));
+        assert_eq!(session.seed, None);
+        assert_eq!(execute(&mut session, "save grimnir"), Ok(()));
+        let saved = session.character.clone().unwrap();
+
+        let mut session = ReplSession::default();
+        assert_eq!(execute(&mut session, "load grimnir"), Ok(()));
+        assert_eq!(serde_json::to_string(&session.character).unwrap(), serde_json::to_string(&Some(saved)).unwrap());
+        assert_eq!((session.careers.as_slice(), session.species.as_str()), (&["random_2".to_string()][..], "dwarf"));
+        assert_eq!(execute(&mut session, "reroll skills"), Ok(()));
+        assert_eq!(execute(&mut session, "load gunther"), Err(CommandError::UnknownNpc));
+        std::fs::remove_dir_all(&library).ok();
+    }
+
+    #[test] 
+    fn app_generate_creature() {
+        let args: Vec<String> = vec!["generate-creature".into(), "wolf".into(), "--seed".into(), "7".into(), "--format".into(), "compact".into()];
+        let sheet = App::new().creature_sheet(&args).unwrap();
+        let lines: Vec<&str> = sheet.lines().collect();
+        // SB 2 + 2 * TB 2 + WPB 2, and one of the optional traits
+        assert!(lines[0].starts_with("Wolf — WS 31 BS 0 S 26 T 23 ") && lines[0].contains(" WP 25 Fel 0 W 8 M 9;"));
+        assert_eq!(lines[1], "Traits: Bestial, Bite +5, Night Vision, Skittish, Stride, Tracker, Infected");
+
+        let command:Vec<String> =vec!["app_name".into(), "generate-creature".into(), "wolf".into(), "--seed".into(), "7".into(), "--format".into(), "compact".into()];
+        assert_eq!(App::new().run(command.into_iter()), Ok(()))
+    }
+
+    #[test] 
+    fn app_unknown_creature() {
+        let command:Vec<String> =vec!["app_name".into(), "generate-creature".into(), "jabberwock".into()];
+        assert_eq!(App::new().run(command.into_iter()).unwrap_err(), CommandError::UnknownCreature)
+    }
+
     #[test] 
     fn app_too_many_arguments() {
         let invalid_command:Vec<String> =vec!["app_name".into(), "generate-npc".into(), "profession".into(), "species".into(), "mine_is_bigger".into()];
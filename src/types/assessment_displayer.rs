@@ -0,0 +1,61 @@
+use crate::models::threat::{Assessment, GroupThreat};
+
+use super::console_settings::ConsoleSettings;
+use super::console_table::{ConsoleTable, TableColumn};
+
+const SIDE: &str = "Side";
+const PARTY: &str = "Party";
+const GROUP: &str = "Group";
+const NEVER: &str = "never";
+
+// Both sides of an assessment side by side, followed by the difficulty rating
+pub struct AssessmentDisplayer {
+    settings: ConsoleSettings,
+}
+
+impl AssessmentDisplayer {
+    pub fn with_settings(settings: ConsoleSettings) -> Self {
+        AssessmentDisplayer { settings }
+    }
+
+    pub fn print(&self, name: &str, assessment: &Assessment) -> String {
+        let (party, group) = (&assessment.party, &assessment.group);
+        let column = |header: &str, value: &dyn Fn(&GroupThreat) -> String| TableColumn::new(header, value(party)).with_value(value(group));
+        let table = ConsoleTable::new(SIDE, &[PARTY, GROUP]).with_columns([
+            column("Members", &|side| side.members.to_string()),
+            column("Wounds", &|side| side.total_wounds.to_string()),
+            column("WS", &|side| format!("{:.0}", side.average_weapon_skill)),
+            column("BS", &|side| format!("{:.0}", side.average_ballistic_skill)),
+            column("TB", &|side| format!("{:.1}", side.average_toughness_bonus)),
+            column("AP", &|side| format!("{:.1}", side.average_armour)),
+            column("Damage", &|side| format!("{:.1}", side.damage_output)),
+            TableColumn::new("Rounds to win", Self::format_rounds(assessment.rounds_to_defeat_group))
+                .with_value(Self::format_rounds(assessment.rounds_to_defeat_party)),
+        ]);
+        format!("=== {} against a party of {} ===\n\n{}\nDifficulty: {}", name, party.members, table.render(&self.settings), assessment.difficulty)
+    }
+
+    fn format_rounds(rounds: f64) -> String {
+        if rounds.is_finite() { format!("{:.1}", rounds) } else { NEVER.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{character::Character, stat_value::StatValue};
+    use crate::types::console_settings::BorderStyle;
+
+    #[test]
+    fn print_assessment() {
+        let fighter = Character { weapon_skill: StatValue::new(40), strength: StatValue::new(30), toughness: StatValue::new(30), wounds: 12, ..Character::default() };
+        let assessment = Assessment::new(&[fighter.clone(), fighter.clone()], &[fighter]);
+        let settings = ConsoleSettings { width: 200, border: BorderStyle::Ascii, color: false };
+        let printed = AssessmentDisplayer::with_settings(settings).print("Bandit Gang", &assessment);
+
+        assert!(printed.starts_with("=== Bandit Gang against a party of 2 ===\n\nSide |Members      |Wounds       |"));
+        assert!(printed.contains("\nParty|2            |24           |40           |"));
+        assert!(printed.ends_with("\nDifficulty: Trivial"));
+        assert_eq!(AssessmentDisplayer::format_rounds(f64::INFINITY), NEVER);
+    }
+}
@@ -0,0 +1,264 @@
+use std::fs;
+use std::path::PathBuf;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::interfaces::npc_store::NpcStore;
+use crate::models::campaign_npc::{CampaignNpc, Relationship};
+use crate::models::saved_npc::SavedNpc;
+use super::command::CommandError;
+use super::data_directory::data_directory;
+use super::npc_library::NpcLibrary;
+
+const CAMPAIGN_FILE: &str = "campaign.db";
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+    CREATE TABLE IF NOT EXISTS npcs (
+        id TEXT PRIMARY KEY,
+        data TEXT NOT NULL,
+        location TEXT,
+        faction TEXT,
+        notes TEXT
+    );
+    CREATE TABLE IF NOT EXISTS tags (
+        npc_id TEXT NOT NULL REFERENCES npcs(id) ON DELETE CASCADE,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (npc_id, tag)
+    );
+    CREATE TABLE IF NOT EXISTS relationships (
+        npc_id TEXT NOT NULL REFERENCES npcs(id) ON DELETE CASCADE,
+        other_id TEXT NOT NULL REFERENCES npcs(id) ON DELETE CASCADE,
+        relationship TEXT NOT NULL,
+        PRIMARY KEY (npc_id, other_id)
+    );
+    CREATE VIRTUAL TABLE IF NOT EXISTS npc_search USING fts5(
+        id UNINDEXED, name, species, careers, talents, tags, location, faction, notes
+    );
+";
+
+// NPCs of a campaign in a SQLite database, with what is known about them and full-text search over it
+pub struct Campaign {
+    path: PathBuf,
+    connection: Connection,
+}
+
+impl Campaign {
+    pub fn open(path: PathBuf) -> Result<Self, CommandError> {
+        if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+            fs::create_dir_all(directory).map_err(|_| CommandError::IoFailure)?;
+        }
+        let connection = Connection::open(&path).map_err(|_| CommandError::IoFailure)?;
+        connection.execute_batch(SCHEMA).map_err(|_| CommandError::IoFailure)?;
+        Ok(Campaign { path, connection })
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        data_directory().map(|directory| directory.join(CAMPAIGN_FILE))
+    }
+
+    pub fn get(&self, id: &str) -> Result<CampaignNpc, CommandError> {
+        let (data, location, faction, notes): (String, Option<String>, Option<String>, Option<String>) = self.connection
+            .query_row("SELECT data, location, faction, notes FROM npcs WHERE id = ?1", params![id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .optional()
+            .map_err(|_| CommandError::IoFailure)?
+            .ok_or(CommandError::UnknownNpc)?;
+        let mut npc: SavedNpc = serde_json::from_str(&data).map_err(|_| CommandError::IoFailure)?;
+        npc.tags = self.tags(id)?;
+        let relationships = self.query("SELECT other_id, relationship FROM relationships WHERE npc_id = ?1 ORDER BY other_id", id, |row| {
+            Ok(Relationship { other: row.get(0)?, relationship: row.get(1)? })
+        })?;
+        Ok(CampaignNpc { npc, location, faction, notes, relationships })
+    }
+
+    pub fn tag(&self, id: &str, tags: &[String]) -> Result<(), CommandError> {
+        self.exists(id)?;
+        for tag in tags {
+            self.connection.execute("INSERT OR IGNORE INTO tags (npc_id, tag) VALUES (?1, ?2)", params![id, tag]).map_err(|_| CommandError::IoFailure)?;
+        }
+        self.index(id)
+    }
+
+    pub fn untag(&self, id: &str, tags: &[String]) -> Result<(), CommandError> {
+        self.exists(id)?;
+        for tag in tags {
+            self.connection.execute("DELETE FROM tags WHERE npc_id = ?1 AND tag = ?2", params![id, tag]).map_err(|_| CommandError::IoFailure)?;
+        }
+        self.index(id)
+    }
+
+    // Only what is given is changed
+    pub fn annotate(&self, id: &str, location: Option<&str>, faction: Option<&str>, notes: Option<&str>) -> Result<(), CommandError> {
+        self.exists(id)?;
+        self.connection.execute("UPDATE npcs SET location = COALESCE(?2, location), faction = COALESCE(?3, faction), notes = COALESCE(?4, notes) WHERE id = ?1",
+                                params![id, location, faction, notes])
+                       .map_err(|_| CommandError::IoFailure)?;
+        self.index(id)
+    }
+
+    // An NPC has one relationship towards another, relating them again replaces it
+    pub fn relate(&self, id: &str, other: &str, relationship: &str) -> Result<(), CommandError> {
+        self.exists(id)?;
+        self.exists(other)?;
+        self.connection.execute("INSERT OR REPLACE INTO relationships (npc_id, other_id, relationship) VALUES (?1, ?2, ?3)", params![id, other, relationship])
+                       .map_err(|_| CommandError::IoFailure)?;
+        Ok(())
+    }
+
+    // Every word has to be found in the name, species, careers, talents, tags, location, faction or notes, best matches first
+    pub fn search(&self, query: &str) -> Result<Vec<CampaignNpc>, CommandError> {
+        let Some(query) = Self::match_query(query) else { return Ok(vec![]) };
+        self.query("SELECT id FROM npc_search WHERE npc_search MATCH ?1 ORDER BY rank", &query, |row| row.get::<_, String>(0))?
+            .iter()
+            .map(|id| self.get(id))
+            .collect()
+    }
+
+    // Words are quoted so nothing typed is taken as query syntax, and match as prefixes so "hunt" finds hunters
+    fn match_query(query: &str) -> Option<String> {
+        let words: Vec<String> = query.split_whitespace()
+                                      .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+                                      .collect();
+        Some(words.join(" ")).filter(|_| !words.is_empty())
+    }
+
+    fn exists(&self, id: &str) -> Result<(), CommandError> {
+        self.connection.query_row("SELECT 1 FROM npcs WHERE id = ?1", params![id], |_| Ok(()))
+                       .optional()
+                       .map_err(|_| CommandError::IoFailure)?
+                       .ok_or(CommandError::UnknownNpc)
+    }
+
+    fn tags(&self, id: &str) -> Result<Vec<String>, CommandError> {
+        self.query("SELECT tag FROM tags WHERE npc_id = ?1 ORDER BY tag", id, |row| row.get(0))
+    }
+
+    // The search row of an NPC is written again whenever anything searchable about it changes
+    fn index(&self, id: &str) -> Result<(), CommandError> {
+        let npc = self.get(id)?;
+        let character = &npc.npc.character;
+        let talents = character.talents.iter().map(|talent| talent.name.as_str()).collect::<Vec<&str>>().join(" ");
+        self.connection.execute("DELETE FROM npc_search WHERE id = ?1", params![id]).map_err(|_| CommandError::IoFailure)?;
+        self.connection.execute("INSERT INTO npc_search (id, name, species, careers, talents, tags, location, faction, notes)
+                                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                                params![id, character.name, character.species, character.formatted_career_history(), talents,
+                                        npc.npc.tags.join(" "), npc.location, npc.faction, npc.notes])
+                       .map_err(|_| CommandError::IoFailure)?;
+        Ok(())
+    }
+
+    fn query<T>(&self, sql: &str, param: &str, row: impl FnMut(&rusqlite::Row) -> rusqlite::Result<T>) -> Result<Vec<T>, CommandError> {
+        let mut statement = self.connection.prepare(sql).map_err(|_| CommandError::IoFailure)?;
+        let rows = statement.query_map(params![param], row).map_err(|_| CommandError::IoFailure)?;
+        rows.collect::<rusqlite::Result<Vec<T>>>().map_err(|_| CommandError::IoFailure)
+    }
+}
+
+impl NpcStore for Campaign {
+    // An NPC saved under an id that is taken replaces the one before, what the campaign knows about it is kept
+    fn save(&self, npc: &SavedNpc) -> Result<PathBuf, CommandError> {
+        if !NpcLibrary::is_valid_id(&npc.id) {
+            Err(CommandError::InvalidArguments)?
+        }
+        let data = serde_json::to_string(npc).map_err(|_| CommandError::IoFailure)?;
+        let transaction = self.connection.unchecked_transaction().map_err(|_| CommandError::IoFailure)?;
+        transaction.execute("INSERT INTO npcs (id, data) VALUES (?1, ?2) ON CONFLICT (id) DO UPDATE SET data = excluded.data", params![npc.id, data])
+                   .map_err(|_| CommandError::IoFailure)?;
+        transaction.execute("DELETE FROM tags WHERE npc_id = ?1", params![npc.id]).map_err(|_| CommandError::IoFailure)?;
+        for tag in &npc.tags {
+            transaction.execute("INSERT OR IGNORE INTO tags (npc_id, tag) VALUES (?1, ?2)", params![npc.id, tag]).map_err(|_| CommandError::IoFailure)?;
+        }
+        self.index(&npc.id)?;
+        transaction.commit().map_err(|_| CommandError::IoFailure)?;
+        Ok(self.path.clone())
+    }
+
+    fn load(&self, id: &str) -> Result<SavedNpc, CommandError> {
+        Ok(self.get(id)?.npc)
+    }
+
+    fn list(&self) -> Result<Vec<SavedNpc>, CommandError> {
+        let mut statement = self.connection.prepare("SELECT id FROM npcs ORDER BY id").map_err(|_| CommandError::IoFailure)?;
+        let ids = statement.query_map([], |row| row.get::<_, String>(0))
+                           .and_then(|rows| rows.collect::<rusqlite::Result<Vec<String>>>())
+                           .map_err(|_| CommandError::IoFailure)?;
+        ids.iter().map(|id| self.load(id)).collect()
+    }
+
+    fn delete(&self, id: &str) -> Result<(), CommandError> {
+        let transaction = self.connection.unchecked_transaction().map_err(|_| CommandError::IoFailure)?;
+        self.exists(id)?;
+        transaction.execute("DELETE FROM npcs WHERE id = ?1", params![id]).map_err(|_| CommandError::IoFailure)?;
+        transaction.execute("DELETE FROM npc_search WHERE id = ?1", params![id]).map_err(|_| CommandError::IoFailure)?;
+        transaction.commit().map_err(|_| CommandError::IoFailure)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{career_step::CareerStep, character::Character, talent::Talent};
+
+    fn campaign(name: &str) -> Campaign {
+        let path = std::env::temp_dir().join(format!("wfrp-npc-campaign-{}-{}.db", name, std::process::id()));
+        fs::remove_file(&path).ok();
+        Campaign::open(path).unwrap()
+    }
+
+    fn saved(id: &str, name: &str, career: &str, talents: &[&str]) -> SavedNpc {
+        let character = Character {
+            name: name.into(),
+            species: "Human".into(),
+            career_history: vec![CareerStep { career: career.into(), level: 2, title: career.into(), completed: true }],
+            talents: talents.iter().map(|talent| Talent { name: talent.to_string(), level: 1, max_level: 1, description: String::new(), magic: None, specialisations: vec![] }).collect(),
+            ..Character::default()
+        };
+        SavedNpc { id: id.into(), tags: vec![], seed: Some(1), careers: vec![], species: "human".into(), options: vec![], character }
+    }
+
+    #[test]
+    fn search_finds_every_word() {
+        let campaign = campaign("search");
+        campaign.save(&saved("ilsa", "Ilsa Weber", "Witch Hunter", &["Menacing"])).unwrap();
+        campaign.save(&saved("gunther", "Gunther Hahn", "Soldier", &["Very Strong"])).unwrap();
+        campaign.annotate("ilsa", Some("Altdorf"), Some("Order of the Silver Hammer"), Some("Hunts the cult of the Purple Hand")).unwrap();
+        campaign.tag("gunther", &["altdorf".into()]).unwrap();
+
+        let ids = |query: &str| campaign.search(query).unwrap().into_iter().map(|npc| npc.npc.id).collect::<Vec<String>>();
+        assert_eq!(ids("witch hunter altdorf"), vec!["ilsa"]);
+        assert_eq!(ids("altdorf").len(), 2);
+        assert_eq!(ids("purple"), vec!["ilsa"]);
+        assert_eq!(ids("very strong"), vec!["gunther"]);
+        assert!(ids("witch NOT \"hunter").is_empty());
+        assert!(ids("  ").is_empty());
+
+        campaign.untag("gunther", &["altdorf".into()]).unwrap();
+        assert_eq!(ids("altdorf"), vec!["ilsa"]);
+        fs::remove_file(&campaign.path).ok();
+    }
+
+    #[test]
+    fn saving_again_keeps_what_is_known() {
+        let campaign = campaign("save");
+        campaign.save(&saved("ilsa", "Ilsa Weber", "Witch Hunter", &[])).unwrap();
+        campaign.save(&saved("gunther", "Gunther Hahn", "Soldier", &[])).unwrap();
+        campaign.annotate("ilsa", None, Some("Order of the Silver Hammer"), None).unwrap();
+        campaign.annotate("ilsa", Some("Altdorf"), None, None).unwrap();
+        campaign.relate("ilsa", "gunther", "sworn enemy").unwrap();
+        assert_eq!(campaign.relate("ilsa", "hilda", "friend").err(), Some(CommandError::UnknownNpc));
+
+        campaign.save(&saved("ilsa", "Ilsa Weber", "Witch Hunter", &["Menacing"])).unwrap();
+        let ilsa = campaign.get("ilsa").unwrap();
+        assert_eq!((ilsa.location.as_deref(), ilsa.faction.as_deref()), (Some("Altdorf"), Some("Order of the Silver Hammer")));
+        assert_eq!(ilsa.relationships, vec![Relationship { other: "gunther".into(), relationship: "sworn enemy".into() }]);
+        assert_eq!(ilsa.npc.character.talents.len(), 1);
+
+        campaign.delete("gunther").unwrap();
+        assert!(campaign.get("ilsa").unwrap().relationships.is_empty());
+        assert_eq!(campaign.list().unwrap().len(), 1);
+        assert_eq!(campaign.delete("gunther").err(), Some(CommandError::UnknownNpc));
+        assert!(campaign.search("soldier").unwrap().is_empty());
+        fs::remove_file(&campaign.path).ok();
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::character::Character;
+use crate::models::{base_attribute::BaseAttribute, character::Character, stat_value::StatValue};
 
 #[derive(Debug, Clone)]
 pub struct CharacterAttributesIter<'a>{
@@ -18,7 +18,11 @@ impl <'a> CharacterAttributesIter<'a> {
 //Just a trash ignore
 pub struct CharacterAttribute {
     pub name: &'static str,
-    pub level: u32
+    pub short_name: &'static str,
+    pub attribute: BaseAttribute,
+    pub initial: u32,
+    pub advances: u32,
+    pub current: u32,
 }
 
 // In the future it should be safeguarded by a macro which checks that all fields are indeed in here
@@ -26,23 +30,24 @@ impl <'a> Iterator for CharacterAttributesIter<'a> {
     type Item = CharacterAttribute;
     fn next(&mut self) -> Option<Self::Item> {
         let field = match self.index {
-            0 =>  ("Weapon Skill",      self.character.weapon_skill),
-            1 =>  ("Ballistic Skill",   self.character.ballistic_skill),
-            2 =>  ("Strength",          self.character.strength),
-            3 =>  ("Toughness",         self.character.toughness),
-            4 =>  ("Initiative",        self.character.initiative),
-            5 =>  ("Agility",           self.character.agility),
-            6 =>  ("Dexterity",         self.character.dexterity),
-            7 =>  ("Intelligence",      self.character.intelligence),
-            8 =>  ("Willpower",         self.character.willpower),
-            9 =>  ("FellowShip",        self.character.fellowship),
-            10 => ("Wounds",            self.character.wounds),
-            11 => ("Movement",          self.character.movement),
+            0 =>  ("Weapon Skill",      "WS",   BaseAttribute::WeaponSkill,    self.character.weapon_skill),
+            1 =>  ("Ballistic Skill",   "BS",   BaseAttribute::BallisticSkill, self.character.ballistic_skill),
+            2 =>  ("Strength",          "S",    BaseAttribute::Strength,       self.character.strength),
+            3 =>  ("Toughness",         "T",    BaseAttribute::Toughness,      self.character.toughness),
+            4 =>  ("Initiative",        "I",    BaseAttribute::Initiative,     self.character.initiative),
+            5 =>  ("Agility",           "Ag",   BaseAttribute::Agility,        self.character.agility),
+            6 =>  ("Dexterity",         "Dex",  BaseAttribute::Dexterity,      self.character.dexterity),
+            7 =>  ("Intelligence",      "Int",  BaseAttribute::Intelligence,   self.character.intelligence),
+            8 =>  ("Willpower",         "WP",   BaseAttribute::Willpower,      self.character.willpower),
+            9 =>  ("FellowShip",        "Fel",  BaseAttribute::Fellowship,     self.character.fellowship),
+            10 => ("Wounds",            "W",    BaseAttribute::Wounds,         StatValue::new(self.character.wounds)),
+            11 => ("Movement",          "M",    BaseAttribute::Movement,       StatValue::new(self.character.movement)),
             _ => return None,
         };
 
         self.index += 1;
-        return Some(CharacterAttribute{name: field.0, level: field.1});
+        let (name, short_name, attribute, value) = field;
+        Some(CharacterAttribute{name, short_name, attribute, initial: value.initial, advances: value.advances, current: value.current()})
     }
 }
 
@@ -52,23 +57,7 @@ mod test {
 
     #[test]
     fn character_attribute_names() {
-        let character = Character {
-            talents: vec![],
-            skills: vec![],
-            trappings: vec![],
-            ballistic_skill: 1,
-            weapon_skill: 1,
-            strength: 1,
-            toughness: 1,
-            initiative: 1,
-            agility: 1,
-            dexterity: 2,
-            intelligence: 1,
-            willpower: 3,
-            fellowship: 3,
-            wounds: 2,
-            movement: 1
-        };
+        let character = Character::default();
 
         assert_eq!(CharacterAttributesIter::new(&character).map(|s| s.name).collect::<Vec<&str>>(),
                    vec!["Weapon Skill",
@@ -84,4 +73,17 @@ mod test {
                         "Wounds",
                         "Movement",]);
     }
+
+    #[test]
+    fn character_attribute_values() {
+        let character = Character {
+            weapon_skill: StatValue{initial: 31, advances: 5},
+            wounds: 12,
+            ..Character::default()
+        };
+        let attributes: Vec<(u32, u32, u32)> = CharacterAttributesIter::new(&character).map(|s| (s.initial, s.advances, s.current)).collect();
+
+        assert_eq!(attributes[0], (31, 5, 36));
+        assert_eq!(attributes[10], (12, 0, 12));
+    }
 }
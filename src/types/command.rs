@@ -1,6 +1,8 @@
 use std::slice::Iter;
 
+use super::command_option::{CommandOption, CommandOptions, TAG_SEPARATOR};
 use super::generate_npc_command_args::GenerateNpcCommandArgs;
+use super::npc_library::NpcLibrary;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommandError {
     InvalidCommand,
@@ -8,55 +10,165 @@ pub enum CommandError {
     EmptyCommand,
     TooFewArguments(usize, usize),
     TooManyArguments(usize, usize),
+    InvalidOption,
+    MissingOptionValue,
+    UnknownProfession,
+    UnknownSpecies,
+    UnknownCreature,
+    UnknownClass,
+    NoCareerAvailable,
+    UnknownRegion,
+    UnknownEncounter,
+    UnknownTalent,
+    UnknownSkill,
+    NoCharacter,
+    IoFailure,
+    MissingSeed,
+    UnknownNpc,
 }
 
 
 impl std::fmt::Display for CommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            &Self::EmptyCommand => {
+        match *self {
+            Self::EmptyCommand => {
                 write!(f, "Command was empty")
             }
-            &Self::InvalidArguments => {
+            Self::InvalidArguments => {
                 write!(f, "Command Arguments Invalid")
             }
-            &Self::InvalidCommand => {
+            Self::InvalidCommand => {
                 write!(f, "Command not defined")
             }
-            &Self::TooFewArguments(given, expected) => {
+            Self::TooFewArguments(given, expected) => {
 
                 write!(f, "Command needs {}, and got {} arguments", given, expected)
             }
-            &Self::TooManyArguments(given, expected) => {
+            Self::TooManyArguments(given, expected) => {
 
                 write!(f, "Command needs {}, and got {} arguments", given, expected)
             }
+            Self::InvalidOption => {
+                write!(f, "Command option not recognized")
+            }
+            Self::MissingOptionValue => {
+                write!(f, "Command option is missing its value")
+            }
+            Self::UnknownProfession => {
+                write!(f, "Profession not known")
+            }
+            Self::UnknownSpecies => {
+                write!(f, "Species not known")
+            }
+            Self::UnknownCreature => {
+                write!(f, "Creature not known")
+            }
+            Self::UnknownClass => {
+                write!(f, "Class not known")
+            }
+            Self::NoCareerAvailable => {
+                write!(f, "No career of the class is open to the species")
+            }
+            Self::UnknownRegion => {
+                write!(f, "Region not known")
+            }
+            Self::UnknownEncounter => {
+                write!(f, "Encounter not known")
+            }
+            Self::UnknownTalent => {
+                write!(f, "Talent not known")
+            }
+            Self::UnknownSkill => {
+                write!(f, "Skill not known")
+            }
+            Self::NoCharacter => {
+                write!(f, "No NPC generated yet")
+            }
+            Self::IoFailure => {
+                write!(f, "Reading or writing failed")
+            }
+            Self::UnknownNpc => {
+                write!(f, "NPC not in the library")
+            }
+            Self::MissingSeed => {
+                write!(f, "Locked sections are kept from the NPC saved under the --save id or generated with --seed")
+            }
         } 
     }
 }
+// --count comes last, generate-batch takes everything before it
+static GENERATE_NPC_OPTIONS: [CommandOption; 20] = [CommandOption::Format, CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor,
+                                                    CommandOption::Income, CommandOption::Name, CommandOption::Gender, CommandOption::Details,
+                                                    CommandOption::Seed, CommandOption::WithPersonality, CommandOption::Prune, CommandOption::Pack,
+                                                    CommandOption::Class, CommandOption::Region, CommandOption::Lock, CommandOption::Save,
+                                                    CommandOption::Tag, CommandOption::Library, CommandOption::Campaign, CommandOption::Count];
+
 #[derive(Debug, Clone, Copy,  PartialEq, Eq)]
 pub enum Command {
     GenerateNpc,
+    GenerateCreature,
+    GenerateBatch,
+    Encounter,
+    Assess,
+    Repl,
+    List,
+    Show,
+    Delete,
+    Npc,
+    Search,
+    Tag,
+    Annotate,
+    Relate,
 }
 
 
 
 impl Command {
     pub fn iterator() -> Iter<'static, Command> {
-        const COMMANDS: [Command; 1] = [Command::GenerateNpc];
+        const COMMANDS: [Command; 10] = [Command::GenerateNpc, Command::GenerateCreature, Command::GenerateBatch, Command::Encounter, Command::Assess, Command::Repl,
+                                         Command::List, Command::Show, Command::Delete, Command::Npc];
         COMMANDS.iter()
     }
 
-    pub fn to_str(&self) -> &'static str {
+    // Commands given after this one, e.g. "npc search"
+    pub fn subcommands(self) -> &'static [Command] {
+        match self {
+            Command::Npc => &[Command::Search, Command::Tag, Command::Annotate, Command::Relate],
+            _ => &[],
+        }
+    }
+
+    // "npc search", as typed on the command line
+    fn full_name(self) -> String {
+        match Command::iterator().find(|command| command.subcommands().contains(&self)) {
+            Some(parent) => format!("{} {}", parent.to_str(), self.to_str()),
+            None => self.to_str().to_string(),
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
         match self {
             Command::GenerateNpc => "generate-npc",
+            Command::GenerateCreature => "generate-creature",
+            Command::GenerateBatch => "generate-batch",
+            Command::Encounter => "encounter",
+            Command::Assess => "assess",
+            Command::Repl => "repl",
+            Command::List => "list",
+            Command::Show => "show",
+            Command::Delete => "delete",
+            Command::Npc => "npc",
+            Command::Search => "search",
+            Command::Tag => "tag",
+            Command::Annotate => "annotate",
+            Command::Relate => "relate",
         }
     }
 
     pub fn from_str(command: &str) -> Result<Self, CommandError> {
         match Command::iterator().find(|command_enum| command_enum.to_str() == command) {
             Some(command) => {
-                Ok(command.clone())
+                Ok(*command)
             }
             None => {
                 Err(CommandError::InvalidCommand)?       
@@ -65,49 +177,171 @@ impl Command {
     }
     
     pub fn parse_command(args: &Vec<String>, arg_offset: Option<usize>)->Result<Command, CommandError> {
-        let arg_offset: usize = if let Some(offset) = arg_offset { offset } else { 0 };
+        let arg_offset: usize = arg_offset.unwrap_or_default();
         match args.get(arg_offset) {
             Some(command) => {
-                Ok(*Command::from_str(command.as_str())?.validate_command_arguments(args, arg_offset + 1)?)
+                Command::from_str(command.as_str())?.parse_subcommand(args, arg_offset + 1)
             }
             None => {
                 Err(CommandError::EmptyCommand)
             }
         }
     }
+
+    fn parse_subcommand(self, args: &Vec<String>, arg_offset: usize)->Result<Command, CommandError> {
+        if self.subcommands().is_empty() {
+            return Ok(*self.validate_command_arguments(args, arg_offset)?);
+        }
+        let subcommand = CommandOptions(args).positional().get(arg_offset).copied().ok_or(CommandError::EmptyCommand)?;
+        match self.subcommands().iter().find(|command| command.to_str() == subcommand) {
+            Some(command) => command.parse_subcommand(args, arg_offset + 1),
+            None => Err(CommandError::InvalidCommand),
+        }
+    }
     
+    // "generate-batch <count> …" is another way of writing "generate-npc … --count <count>"
+    pub fn batch_as_generate_npc(args: &Vec<String>) -> Vec<String> {
+        let positional = CommandOptions(args).positional_indices();
+        let (command, count) = (positional.first().copied(), positional.get(1).copied());
+        let mut generate_npc_args: Vec<String> = args.iter()
+                                                     .enumerate()
+                                                     .filter(|(index, _)| Some(*index) != count)
+                                                     .map(|(index, arg)| if Some(index) == command { Command::GenerateNpc.to_str().to_string() } else { arg.clone() })
+                                                     .collect();
+        if let Some(count) = count {
+            generate_npc_args.extend([CommandOption::Count.to_str().to_string(), args[count].clone()]);
+        }
+        generate_npc_args
+    }
+
     pub fn command_description(&self) -> String {
         let args_desc = match self {
             Command::GenerateNpc => "<professions> <species>",
+            Command::GenerateCreature => "<creature>",
+            Command::GenerateBatch => "<count> <professions> <species>",
+            Command::Encounter => "<encounter>",
+            Command::Assess => "<encounter> <party professions>",
+            Command::Repl | Command::List | Command::Npc => "",
+            Command::Show | Command::Delete | Command::Annotate => "<id>",
+            Command::Search => "<query>",
+            Command::Tag => "<id> <tag>[,...]",
+            Command::Relate => "<id> <other id> <relationship>",
         };
         
-        format!("{} [{}]", self.to_str(), args_desc)
+        let options_desc: String = self.supported_options()
+                                       .iter()
+                                       .map(|option| format!(" [{}]", option.option_description()))
+                                       .collect();
+
+        match args_desc {
+            "" => format!("{}{}", self.full_name(), options_desc),
+            args_desc => format!("{} [{}]{}", self.full_name(), args_desc, options_desc),
+        }
+    }
+
+    pub fn supported_options(&self) -> &'static [CommandOption] {
+        match self {
+            Command::GenerateNpc => &GENERATE_NPC_OPTIONS,
+            Command::GenerateCreature => &[CommandOption::Format, CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor,
+                                          CommandOption::Name, CommandOption::Seed],
+            // the count is given as the first argument instead
+            Command::GenerateBatch => &GENERATE_NPC_OPTIONS[..GENERATE_NPC_OPTIONS.len() - 1],
+            Command::Encounter => &[CommandOption::Format, CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor,
+                                   CommandOption::Income, CommandOption::Details, CommandOption::Seed, CommandOption::WithPersonality,
+                                   CommandOption::Prune, CommandOption::Pack, CommandOption::Class, CommandOption::Region],
+            Command::Assess => &[CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor, CommandOption::Seed,
+                                CommandOption::Class, CommandOption::Region],
+            Command::Repl => &[CommandOption::Format, CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor,
+                              CommandOption::Income, CommandOption::Gender, CommandOption::Details, CommandOption::WithPersonality,
+                              CommandOption::Prune, CommandOption::Pack, CommandOption::Class, CommandOption::Region, CommandOption::Library,
+                              CommandOption::Campaign],
+            Command::List => &[CommandOption::Species, CommandOption::Career, CommandOption::Tag, CommandOption::Library, CommandOption::Campaign],
+            Command::Show => &[CommandOption::Format, CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor, CommandOption::Library,
+                              CommandOption::Campaign],
+            Command::Delete => &[CommandOption::Library, CommandOption::Campaign],
+            Command::Npc => &[],
+            Command::Search | Command::Relate => &[CommandOption::Campaign],
+            Command::Tag => &[CommandOption::Remove, CommandOption::Campaign],
+            Command::Annotate => &[CommandOption::Location, CommandOption::Faction, CommandOption::Notes, CommandOption::Campaign],
+        }
     }
 
     fn command_params_count(&self) -> usize {
         match self {
             Command::GenerateNpc => 2,
+            Command::GenerateCreature => 1,
+            Command::GenerateBatch => 3,
+            Command::Encounter => 1,
+            Command::Assess => 2,
+            Command::Repl | Command::List | Command::Npc => 0,
+            Command::Show | Command::Delete | Command::Search | Command::Annotate => 1,
+            Command::Tag => 2,
+            Command::Relate => 3,
         }
     }
 
     fn validate_command_arguments(&self, args: &Vec<String>, arg_offset: usize)->Result<&Self, CommandError> {
-        if args.len() > self.command_params_count() + arg_offset {
-            Err(CommandError::TooManyArguments(args.len().max(arg_offset) - arg_offset, self.command_params_count()))?
+        let positional_count = CommandOptions(args).positional().len();
+        if positional_count > self.command_params_count() + arg_offset {
+            Err(CommandError::TooManyArguments(positional_count.max(arg_offset) - arg_offset, self.command_params_count()))?
         }
-        else if args.len() < self.command_params_count() + arg_offset {
-            Err(CommandError::TooFewArguments(args.len().max(arg_offset) - arg_offset, self.command_params_count()))?
+        else if positional_count < self.command_params_count() + arg_offset {
+            Err(CommandError::TooFewArguments(positional_count.max(arg_offset) - arg_offset, self.command_params_count()))?
         };
+        CommandOptions(args).validate(self.supported_options())?;
 
         match self {
             Command::GenerateNpc => {
                 let arg_offset = Some(1);
 
+                GenerateNpcCommandArgs(args).validate_professions(arg_offset)?;
+                GenerateNpcCommandArgs(args).validate_species(arg_offset)?;
+            }
+            Command::GenerateCreature | Command::Encounter => {
+                Self::validate_id(CommandOptions(args).positional()[arg_offset])?;
+            }
+            Command::Assess => {
+                Self::validate_id(CommandOptions(args).positional()[arg_offset])?;
+                GenerateNpcCommandArgs(args).validate_professions(Some(arg_offset + 1))?;
+            }
+            Command::Repl | Command::List | Command::Npc | Command::Search => {}
+            Command::Show | Command::Delete | Command::Annotate => {
+                if !NpcLibrary::is_valid_id(CommandOptions(args).positional()[arg_offset]) {
+                    Err(CommandError::InvalidArguments)?
+                }
+            }
+            Command::Tag => {
+                let positional = CommandOptions(args).positional();
+                if !NpcLibrary::is_valid_id(positional[arg_offset]) || positional[arg_offset + 1].split(TAG_SEPARATOR).any(str::is_empty) {
+                    Err(CommandError::InvalidArguments)?
+                }
+            }
+            Command::Relate => {
+                let positional = CommandOptions(args).positional();
+                if !positional[arg_offset..arg_offset + 2].iter().all(|id| NpcLibrary::is_valid_id(id)) || positional[arg_offset + 2].trim().is_empty() {
+                    Err(CommandError::InvalidArguments)?
+                }
+            }
+            Command::GenerateBatch => {
+                if !CommandOptions(args).positional()[arg_offset].parse::<usize>().is_ok_and(|count| count > 0) {
+                    Err(CommandError::InvalidArguments)?
+                }
+                let arg_offset = Some(arg_offset + 1);
+
                 GenerateNpcCommandArgs(args).validate_professions(arg_offset)?;
                 GenerateNpcCommandArgs(args).validate_species(arg_offset)?;
             }
         }
         Ok(self)
     }
+
+    // Data ids such as "giant_rat" or "bandit_gang"
+    fn validate_id(id: &str)->Result<(), CommandError> {
+        if id.is_empty() || !id.chars().all(|c| c.is_lowercase() || c == '_') {
+            Err(CommandError::InvalidArguments)?
+        }
+        Ok(())
+    }
 }
 #[cfg(test)]
 mod test {
@@ -136,6 +370,118 @@ mod test {
         assert_eq!(Command::parse_command(&args, None), Err(CommandError::TooManyArguments(3, 2)));
     }
 
+    #[test] 
+    fn parse_generate_npc_command_with_format() {
+        let args:Vec<String> =vec!["generate-npc".into(), "--format".into(), "compact".into(), "soldier_2".into(), "human".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateNpc));
+    }
+
+    #[test] 
+    fn parse_generate_npc_command_invalid_format() {
+        let args:Vec<String> =vec!["generate-npc".into(), "soldier_2".into(), "human".into(), "--format".into(), "scroll".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+    }
+
+    #[test] 
+    fn parse_generate_npc_command_with_random_career() {
+        let args:Vec<String> =vec!["generate-npc".into(), "random random_2".into(), "dwarf".into(), "--class".into(), "warriors".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateNpc));
+    }
+
+    #[test] 
+    fn parse_generate_npc_command_with_random_species() {
+        let args:Vec<String> =vec!["generate-npc".into(), "soldier_1".into(), "random".into(), "--region".into(), "the_moot".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateNpc));
+        let args:Vec<String> =vec!["generate-npc".into(), "soldier_1".into(), "high_elf".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateNpc));
+    }
+
+    #[test] 
+    fn parse_generate_creature_command() {
+        let args:Vec<String> =vec!["generate-creature".into(), "giant_rat".into(), "--seed".into(), "3".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateCreature));
+        let args:Vec<String> =vec!["generate-creature".into(), "Wolf".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+    }
+
+    #[test] 
+    fn parse_generate_batch_command() {
+        let args:Vec<String> =vec!["generate-batch".into(), "12".into(), "soldier_1".into(), "human".into(), "--seed".into(), "3".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateBatch));
+        let args:Vec<String> =vec!["generate-batch".into(), "0".into(), "soldier_1".into(), "human".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+        let args:Vec<String> =vec!["generate-batch".into(), "2".into(), "soldier_1".into(), "human".into(), "--count".into(), "3".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidOption));
+    }
+
+    #[test] 
+    fn batch_is_generate_npc_with_count() {
+        let args:Vec<String> =vec!["generate-batch".into(), "--seed".into(), "3".into(), "3".into(), "soldier_1".into(), "human".into(), "--save".into(), "ilsa".into()];
+        assert_eq!(Command::batch_as_generate_npc(&args), vec!["generate-npc", "--seed", "3", "soldier_1", "human", "--save", "ilsa", "--count", "3"]);
+        assert_eq!(Command::GenerateBatch.supported_options().len() + 1, Command::GenerateNpc.supported_options().len());
+    }
+
+    #[test] 
+    fn parse_assess_command() {
+        let args:Vec<String> =vec!["assess".into(), "bandit_gang".into(), "soldier_2 wizard_1 random_2".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Assess));
+        let args:Vec<String> =vec!["assess".into(), "bandit_gang".into(), "soldier".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+    }
+
+    #[test] 
+    fn parse_repl_command() {
+        let args:Vec<String> =vec!["repl".into(), "--format".into(), "compact".into(), "--details".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Repl));
+        let args:Vec<String> =vec!["repl".into(), "--seed".into(), "3".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidOption));
+        assert_eq!(Command::Repl.command_description().split(' ').next(), Some("repl"));
+    }
+
+    #[test] 
+    fn parse_library_commands() {
+        let args:Vec<String> =vec!["list".into(), "--species".into(), "dwarf".into(), "--tag".into(), "altdorf".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::List));
+        let args:Vec<String> =vec!["show".into(), "grimnir".into(), "--format".into(), "compact".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Show));
+        let args:Vec<String> =vec!["delete".into(), "../grimnir".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+        let args:Vec<String> =vec!["generate-npc".into(), "soldier_1".into(), "dwarf".into(), "--save".into(), "grimnir".into(), "--tag".into(), "altdorf".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::GenerateNpc));
+    }
+
+    #[test] 
+    fn parse_campaign_commands() {
+        let args:Vec<String> =vec!["npc".into(), "search".into(), "witch hunter altdorf".into(), "--campaign".into(), "enemy_within.db".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Search));
+        let args:Vec<String> =vec!["npc".into(), "tag".into(), "ilsa".into(), "altdorf,witch hunters".into(), "--remove".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Tag));
+        let args:Vec<String> =vec!["npc".into(), "tag".into(), "ilsa".into(), "altdorf,".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+        let args:Vec<String> =vec!["npc".into(), "annotate".into(), "ilsa".into(), "--location".into(), "Altdorf".into(), "--notes".into(), "Owes Gunther".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Annotate));
+        let args:Vec<String> =vec!["npc".into(), "relate".into(), "ilsa".into(), "gunther".into(), "sworn enemy".into()];
+        assert_eq!(Command::parse_command(&args, None),Ok(Command::Relate));
+        let args:Vec<String> =vec!["npc".into(), "relate".into(), "ilsa".into(), "../gunther".into(), "rival".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidArguments));
+        let args:Vec<String> =vec!["search".into(), "witch hunter altdorf".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidCommand));
+    }
+
+    #[test] 
+    fn parse_npc_command_without_subcommand() {
+        let args:Vec<String> =vec!["npc".into(), "--campaign".into(), "enemy_within.db".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::EmptyCommand));
+        let args:Vec<String> =vec!["npc".into(), "generate-npc".into(), "soldier_1".into(), "dwarf".into()];
+        assert_eq!(Command::parse_command(&args, None), Err(CommandError::InvalidCommand));
+    }
+
+    #[test] 
+    fn npc_subcommands_are_described_under_npc() {
+        assert!(Command::Search.command_description().starts_with("npc search [<query>]"));
+        assert_eq!(Command::Npc.subcommands(), &[Command::Search, Command::Tag, Command::Annotate, Command::Relate]);
+    }
+
     #[test] 
     fn parse_command_command_missing() {
         let args:Vec<String> =vec![];
@@ -0,0 +1,320 @@
+use std::slice::Iter;
+
+use crate::models::{character_section::CharacterSection, gender::Gender};
+use super::command::CommandError;
+use super::display_format::DisplayFormat;
+use super::npc_library::NpcLibrary;
+
+const OPTION_PREFIX: &str = "--";
+pub const TAG_SEPARATOR: char = ',';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOption {
+    Format,
+    Width,
+    Unicode,
+    NoColor,
+    Income,
+    Name,
+    Gender,
+    Details,
+    Seed,
+    WithPersonality,
+    Prune,
+    Pack,
+    Class,
+    Region,
+    Count,
+    Lock,
+    Save,
+    Tag,
+    Species,
+    Career,
+    Library,
+    Campaign,
+    Remove,
+    Location,
+    Faction,
+    Notes,
+}
+
+impl CommandOption {
+    pub fn iterator() -> Iter<'static, CommandOption> {
+        const OPTIONS: [CommandOption; 26] = [CommandOption::Format, CommandOption::Width, CommandOption::Unicode, CommandOption::NoColor,
+                                             CommandOption::Income, CommandOption::Name, CommandOption::Gender, CommandOption::Details,
+                                             CommandOption::Seed, CommandOption::WithPersonality, CommandOption::Prune, CommandOption::Pack,
+                                             CommandOption::Class, CommandOption::Region, CommandOption::Count, CommandOption::Lock,
+                                             CommandOption::Save, CommandOption::Tag, CommandOption::Species, CommandOption::Career,
+                                             CommandOption::Library, CommandOption::Campaign, CommandOption::Remove, CommandOption::Location,
+                                             CommandOption::Faction, CommandOption::Notes];
+        OPTIONS.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            CommandOption::Format => "--format",
+            CommandOption::Width => "--width",
+            CommandOption::Unicode => "--unicode",
+            CommandOption::NoColor => "--no-color",
+            CommandOption::Income => "--income",
+            CommandOption::Name => "--name",
+            CommandOption::Gender => "--gender",
+            CommandOption::Details => "--details",
+            CommandOption::Seed => "--seed",
+            CommandOption::WithPersonality => "--with-personality",
+            CommandOption::Prune => "--prune",
+            CommandOption::Pack => "--pack",
+            CommandOption::Class => "--class",
+            CommandOption::Region => "--region",
+            CommandOption::Count => "--count",
+            CommandOption::Lock => "--lock",
+            CommandOption::Save => "--save",
+            CommandOption::Tag => "--tag",
+            CommandOption::Species => "--species",
+            CommandOption::Career => "--career",
+            CommandOption::Library => "--library",
+            CommandOption::Campaign => "--campaign",
+            CommandOption::Remove => "--remove",
+            CommandOption::Location => "--location",
+            CommandOption::Faction => "--faction",
+            CommandOption::Notes => "--notes",
+        }
+    }
+
+    pub fn from_str(option: &str) -> Result<Self, CommandError> {
+        CommandOption::iterator()
+            .find(|option_enum| option_enum.to_str() == option)
+            .copied()
+            .ok_or(CommandError::InvalidOption)
+    }
+
+    pub fn is_option(arg: &str) -> bool {
+        arg.starts_with(OPTION_PREFIX)
+    }
+
+    pub fn takes_value(self) -> bool {
+        match self {
+            CommandOption::Format | CommandOption::Width | CommandOption::Name | CommandOption::Gender | CommandOption::Seed
+            | CommandOption::Class | CommandOption::Region | CommandOption::Count | CommandOption::Lock | CommandOption::Save
+            | CommandOption::Tag | CommandOption::Species | CommandOption::Career | CommandOption::Library | CommandOption::Campaign
+            | CommandOption::Location | CommandOption::Faction | CommandOption::Notes => true,
+            CommandOption::Unicode | CommandOption::NoColor | CommandOption::Income | CommandOption::Details | CommandOption::WithPersonality
+            | CommandOption::Prune | CommandOption::Pack | CommandOption::Remove => false,
+        }
+    }
+
+    pub fn option_description(self) -> String {
+        match self {
+            CommandOption::Format => format!("{} <{}>", self.to_str(), DisplayFormat::formatted_list()),
+            CommandOption::Width => format!("{} <columns>", self.to_str()),
+            CommandOption::Name => format!("{} <name>", self.to_str()),
+            CommandOption::Seed => format!("{} <number>", self.to_str()),
+            CommandOption::Gender => format!("{} <{}>", self.to_str(), Gender::formatted_list()),
+            CommandOption::Class => format!("{} <class>", self.to_str()),
+            CommandOption::Region => format!("{} <region>", self.to_str()),
+            CommandOption::Count => format!("{} <number>", self.to_str()),
+            CommandOption::Lock => format!("{} <{}>[,...]", self.to_str(), CharacterSection::formatted_list()),
+            CommandOption::Save => format!("{} <id>", self.to_str()),
+            CommandOption::Tag => format!("{} <tag>[,...]", self.to_str()),
+            CommandOption::Species => format!("{} <species>", self.to_str()),
+            CommandOption::Career => format!("{} <career>", self.to_str()),
+            CommandOption::Library => format!("{} <directory>", self.to_str()),
+            CommandOption::Campaign => format!("{} <database>", self.to_str()),
+            CommandOption::Location => format!("{} <location>", self.to_str()),
+            CommandOption::Faction => format!("{} <faction>", self.to_str()),
+            CommandOption::Notes => format!("{} <notes>", self.to_str()),
+            CommandOption::Unicode | CommandOption::NoColor | CommandOption::Income | CommandOption::Details | CommandOption::WithPersonality
+            | CommandOption::Prune | CommandOption::Pack | CommandOption::Remove => self.to_str().to_string(),
+        }
+    }
+
+    fn validate_value(self, value: &str) -> Result<(), CommandError> {
+        match self {
+            CommandOption::Format => DisplayFormat::from_str(value).map(|_| ()),
+            CommandOption::Width | CommandOption::Count => match value.parse::<usize>() {
+                Ok(width) if width > 0 => Ok(()),
+                _ => Err(CommandError::InvalidArguments),
+            },
+            CommandOption::Seed => value.parse::<u64>().map(|_| ()).map_err(|_| CommandError::InvalidArguments),
+            CommandOption::Gender => value.parse::<Gender>().map(|_| ()).map_err(|_| CommandError::InvalidArguments),
+            CommandOption::Class => match value.chars().all(|c| c.is_lowercase()) {
+                true => Ok(()),
+                false => Err(CommandError::InvalidArguments),
+            },
+            CommandOption::Region | CommandOption::Species | CommandOption::Career => match value.chars().all(|c| c.is_lowercase() || c == '_') {
+                true => Ok(()),
+                false => Err(CommandError::InvalidArguments),
+            },
+            CommandOption::Lock => CharacterSection::parse_list(value).map(|_| ()).map_err(|_| CommandError::InvalidArguments),
+            CommandOption::Save => match NpcLibrary::is_valid_id(value) {
+                true => Ok(()),
+                false => Err(CommandError::InvalidArguments),
+            },
+            CommandOption::Tag => match value.split(TAG_SEPARATOR).all(|tag| !tag.is_empty()) {
+                true => Ok(()),
+                false => Err(CommandError::InvalidArguments),
+            },
+            CommandOption::Unicode | CommandOption::NoColor | CommandOption::Income | CommandOption::Details | CommandOption::WithPersonality
+            | CommandOption::Prune | CommandOption::Pack | CommandOption::Name | CommandOption::Library
+            | CommandOption::Campaign | CommandOption::Remove | CommandOption::Location | CommandOption::Faction | CommandOption::Notes => Ok(()),
+        }
+    }
+}
+
+// Splits raw command line arguments into positional arguments and `--option [value]` pairs
+pub struct CommandOptions<'a>(pub &'a Vec<String>);
+
+impl<'a> CommandOptions<'a> {
+    pub fn positional(&self) -> Vec<&'a String> {
+        self.positional_indices().into_iter().map(|index| &self.0[index]).collect()
+    }
+
+    // Where the positional arguments are among all the arguments
+    pub fn positional_indices(&self) -> Vec<usize> {
+        let mut positional = Vec::new();
+        let mut args = self.0.iter().enumerate();
+        while let Some((index, arg)) = args.next() {
+            if !CommandOption::is_option(arg) {
+                positional.push(index);
+            }
+            else if CommandOption::from_str(arg).is_ok_and(|option| option.takes_value()) {
+                args.next();
+            }
+        }
+        positional
+    }
+
+    pub fn value(&self, option: CommandOption) -> Option<&'a String> {
+        let position = self.0.iter().position(|arg| arg == option.to_str())?;
+        self.0.get(position + 1)
+    }
+
+    pub fn is_set(&self, option: CommandOption) -> bool {
+        self.0.iter().any(|arg| arg == option.to_str())
+    }
+
+    // The given options as they were passed, values included
+    pub fn retained(&self, options: &[CommandOption]) -> Vec<String> {
+        let mut retained = Vec::new();
+        let mut args = self.0.iter();
+        while let Some(arg) = args.next() {
+            let Ok(option) = CommandOption::from_str(arg) else { continue };
+            let value = if option.takes_value() { args.next() } else { None };
+            if options.contains(&option) {
+                retained.push(arg.clone());
+                retained.extend(value.cloned());
+            }
+        }
+        retained
+    }
+
+    pub fn validate(&self, supported: &[CommandOption]) -> Result<&Self, CommandError> {
+        let mut args = self.0.iter();
+        while let Some(arg) = args.next() {
+            if !CommandOption::is_option(arg) {
+                continue;
+            }
+            let option = CommandOption::from_str(arg)?;
+            if !supported.contains(&option) {
+                Err(CommandError::InvalidOption)?
+            }
+            if option.takes_value() {
+                match args.next() {
+                    Some(value) if !CommandOption::is_option(value) => option.validate_value(value)?,
+                    _ => Err(CommandError::MissingOptionValue)?,
+                }
+            }
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn positional_skips_options_and_values() {
+        let args: Vec<String> = vec!["generate-npc".into(), "--format".into(), "compact".into(), "soldier_2".into(), "human".into()];
+        assert_eq!(CommandOptions(&args).positional(), vec!["generate-npc", "soldier_2", "human"]);
+        assert_eq!(CommandOptions(&args).positional_indices(), vec![0, 3, 4]);
+    }
+
+    #[test]
+    fn option_value() {
+        let args: Vec<String> = vec!["soldier_2".into(), "human".into(), "--format".into(), "compact".into()];
+        assert_eq!(CommandOptions(&args).value(CommandOption::Format).map(|s| s.as_str()), Some("compact"));
+    }
+
+    #[test]
+    fn validate_unknown_option() {
+        let args: Vec<String> = vec!["soldier_2".into(), "--colour".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Format]).err(), Some(CommandError::InvalidOption));
+    }
+
+    #[test]
+    fn validate_missing_value() {
+        let args: Vec<String> = vec!["soldier_2".into(), "--format".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Format]).err(), Some(CommandError::MissingOptionValue));
+    }
+
+    #[test]
+    fn validate_flags_do_not_consume_values() {
+        let args: Vec<String> = vec!["--no-color".into(), "soldier_2".into(), "--unicode".into(), "human".into()];
+        assert_eq!(CommandOptions(&args).positional(), vec!["soldier_2", "human"]);
+        assert!(CommandOptions(&args).validate(&[CommandOption::Unicode, CommandOption::NoColor]).is_ok());
+    }
+
+    #[test]
+    fn validate_invalid_width() {
+        let args: Vec<String> = vec!["--width".into(), "0".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Width]).err(), Some(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn validate_gender() {
+        let args: Vec<String> = vec!["--gender".into(), "neutral".into(), "--name".into(), "Hans Gruber".into()];
+        assert!(CommandOptions(&args).validate(&[CommandOption::Gender, CommandOption::Name]).is_ok());
+        let args: Vec<String> = vec!["--gender".into(), "any".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Gender]).err(), Some(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn validate_class() {
+        let args: Vec<String> = vec!["random_2".into(), "dwarf".into(), "--class".into(), "rogues".into()];
+        assert_eq!(CommandOptions(&args).positional(), vec!["random_2", "dwarf"]);
+        assert!(CommandOptions(&args).validate(&[CommandOption::Class]).is_ok());
+        let args: Vec<String> = vec!["--class".into(), "Rogues".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Class]).err(), Some(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn retained_options_keep_their_values() {
+        let args: Vec<String> = vec!["soldier_1".into(), "--details".into(), "--seed".into(), "3".into(), "--region".into(), "tilea".into()];
+        assert_eq!(CommandOptions(&args).retained(&[CommandOption::Details, CommandOption::Region]), vec!["--details", "--region", "tilea"]);
+    }
+
+    #[test]
+    fn validate_library_options() {
+        let args: Vec<String> = vec!["--save".into(), "grimnir".into(), "--tag".into(), "altdorf,bodyguard".into()];
+        assert!(CommandOptions(&args).validate(&[CommandOption::Save, CommandOption::Tag]).is_ok());
+        let args: Vec<String> = vec!["--save".into(), "../grimnir".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Save]).err(), Some(CommandError::InvalidArguments));
+        let args: Vec<String> = vec!["--tag".into(), "altdorf,".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Tag]).err(), Some(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn validate_lock() {
+        let args: Vec<String> = vec!["--lock".into(), "characteristics,name".into()];
+        assert!(CommandOptions(&args).validate(&[CommandOption::Lock]).is_ok());
+        let args: Vec<String> = vec!["--lock".into(), "characteristics,hair".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Lock]).err(), Some(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn validate_invalid_value() {
+        let args: Vec<String> = vec!["soldier_2".into(), "--format".into(), "fancy".into()];
+        assert_eq!(CommandOptions(&args).validate(&[CommandOption::Format]).err(), Some(CommandError::InvalidArguments));
+    }
+}
@@ -0,0 +1,272 @@
+use crate::interfaces::character_displayer::CharacterDisplayer;
+use crate::models::{armour::HitLocation, character::Character, size::Size, weapon::Weapon, details::Details, personality::Personality, spell::Spell, talent::Talent, skill::Skill, trapping::Trapping};
+use crate::types::character_attributes_iter::CharacterAttributesIter;
+
+const SECTION_DELIMETER: &str = "; ";
+const ELEMENT_DELIMETER: &str = ", ";
+const HEADER_DELIMETER: &str = " — ";
+
+// Statblock squeezed into two lines, meant for the margins of an adventure script
+pub struct CompactCharacterDisplayer {}
+
+impl CompactCharacterDisplayer {
+    pub fn new() -> Self {
+        CompactCharacterDisplayer{}
+    }
+
+    fn create_attributes_line(character: &Character) -> String {
+        let attributes = CharacterAttributesIter::new(character)
+            .map(|attribute| format!("{} {}", attribute.short_name, attribute.current))
+            .collect::<Vec<String>>()
+            .join(" ");
+        // "Human Soldier 2"
+        let species_and_careers = [character.species.clone(), character.formatted_career_history()].into_iter()
+                                                                                                   .filter(|part| !part.is_empty())
+                                                                                                   .collect::<Vec<String>>()
+                                                                                                   .join(" ");
+        let header: Vec<String> = [
+            Some(character.name.clone()).filter(|name| !name.is_empty()),
+            character.status.map(|status| status.to_string()),
+            Some(species_and_careers).filter(|species_and_careers| !species_and_careers.is_empty()),
+            character.affiliation.clone(),
+        ].into_iter().flatten().collect();
+        let header = if header.is_empty() { String::new() } else { format!("{}{}", header.join(ELEMENT_DELIMETER), HEADER_DELIMETER) };
+        format!("{}{}{}Fate {}, Fortune {}, Resilience {}, Resolve {}, Corruption {}{}Enc {}/{}{}",
+                header, attributes, SECTION_DELIMETER, character.fate, character.fortune, character.resilience, character.resolve, character.corruption,
+                SECTION_DELIMETER, character.total_encumbrance(), character.encumbrance_limit(), if character.is_overloaded() { " overloaded" } else { "" })
+    }
+
+    fn create_section<T>(header: &str, data: &[T], element: impl Fn(&T) -> String) -> Option<String> {
+        if data.is_empty() {
+            return None;
+        }
+        Some(format!("{}: {}", header, data.iter().map(element).collect::<Vec<String>>().join(ELEMENT_DELIMETER)))
+    }
+
+    fn format_details(details: &Details) -> String {
+        let mut details_line = format!("Details: Age {}, Height {}, Eyes {}, Hair {}, Born {}",
+                                       details.age, details.formatted_height(), details.eyes, details.hair, details.birthplace);
+        if let Some(mark) = &details.distinguishing_mark {
+            details_line.push_str(ELEMENT_DELIMETER);
+            details_line.push_str(mark);
+        }
+        details_line
+    }
+
+    fn format_personality(personality: &Personality) -> String {
+        format!("Roleplaying: {}{}Motivation: {}{}Ambitions: {} / {}{}Secret: {}",
+                personality.traits.join(ELEMENT_DELIMETER), SECTION_DELIMETER, personality.motivation, SECTION_DELIMETER,
+                personality.short_term_ambition, personality.long_term_ambition, SECTION_DELIMETER, personality.secret)
+    }
+
+    fn format_armour(character: &Character) -> String {
+        format!("Armour: {}", HitLocation::iterator()
+                                  .map(|location| format!("{} {}", location.to_str(), character.armour_points(*location)))
+                                  .collect::<Vec<String>>()
+                                  .join(ELEMENT_DELIMETER))
+    }
+
+    fn format_spell(spell: &Spell) -> String {
+        match spell.cn {
+            Some(cn) => format!("{} CN {}", spell.name, cn),
+            None => spell.name.clone(),
+        }
+    }
+
+    fn format_skill(character: &Character, skill: &Skill) -> String {
+        format!("{} {}", skill.name, character.skill_value(skill).current())
+    }
+
+    fn format_talent(talent: &Talent) -> String {
+        if talent.level > 1 {
+            format!("{} {}", talent.name, talent.level)
+        }
+        else {
+            talent.name.clone()
+        }
+    }
+
+    fn format_trapping(trapping: &Trapping) -> String {
+        if trapping.count > 1 {
+            format!("{} ({})", trapping.name, trapping.count)
+        }
+        else {
+            trapping.name.clone()
+        }
+    }
+}
+
+impl CharacterDisplayer<String> for CompactCharacterDisplayer {
+    fn print(&self, character: &Character) -> String {
+        let sections: Vec<String> = [
+            character.details.as_ref().map(Self::format_details),
+            Self::create_section("Traits", &character.traits, |creature_trait| character.formatted_trait(creature_trait)),
+            Self::create_section("Skills", &character.skills, |skill| Self::format_skill(character, skill)),
+            Self::create_section("Talents", &character.talents, Self::format_talent),
+            Self::create_section("Trappings", &character.trappings, Self::format_trapping),
+            Self::create_section("Weapons", &character.weapons().collect::<Vec<&Weapon>>(), |weapon| format!("{} {}", weapon.name, character.weapon_damage(weapon))),
+            Some(Self::format_armour(character)).filter(|_| character.armour().next().is_some() || character.is_armoured()),
+            Some(format!("Size: {} ({})", character.size, character.size.combat_modifiers())).filter(|_| character.size != Size::Average),
+        ].into_iter()
+         .chain(character.spells.iter().map(|lore| Self::create_section(&format!("Spells ({})", lore.name), &lore.spells, Self::format_spell)))
+         .flatten()
+         .collect();
+
+        let lines: Vec<String> = [
+            Some(Self::create_attributes_line(character)),
+            Some(sections.join(SECTION_DELIMETER)).filter(|sections| !sections.is_empty()),
+            character.personality.as_ref().map(Self::format_personality),
+        ].into_iter().flatten().collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{armour::Armour, base_attribute::BaseAttribute, career_step::CareerStep, creature_trait::CreatureTrait, spell::Lore, stat_value::StatValue};
+
+    fn character() -> Character {
+        Character {
+            talents: vec![Talent{name: "Very Strong".into(), level: 1, max_level: 1, description: "Desc".into(), magic: None, specialisations: vec![]},
+                          Talent{name: "Strike Mighty Blow".into(), level: 2, max_level: 4, description: "Desc".into(), magic: None, specialisations: vec![]},],
+            skills: vec![Skill{name: "Melee (Basic)".into(), advances: 5, base_attribute: BaseAttribute::WeaponSkill},
+                         Skill{name: "Dodge".into(), advances: 7, base_attribute: BaseAttribute::Agility},],
+            trappings: vec![Trapping::new("Hand Weapon", 1), Trapping::new("Torch", 3),],
+            ballistic_skill: StatValue::new(35),
+            weapon_skill: StatValue::new(42),
+            strength: StatValue::new(38),
+            toughness: StatValue::new(33),
+            initiative: StatValue::new(30),
+            agility: StatValue::new(31),
+            dexterity: StatValue::new(29),
+            intelligence: StatValue::new(27),
+            willpower: StatValue::new(36),
+            fellowship: StatValue::new(25),
+            wounds: 12,
+            movement: 4,
+            size: Size::Average,
+            fate: 2,
+            fortune: 3,
+            resilience: 1,
+            resolve: 1,
+            corruption: 0,
+            status: None,
+            career_history: vec![],
+            affiliation: None,
+            name: String::new(),
+            species: String::new(),
+            details: None,
+            personality: None,
+            spells: vec![],
+            traits: vec![],
+        }
+    }
+
+    #[test]
+    fn print_compact_character() {
+        assert_eq!(CompactCharacterDisplayer::new().print(&character()),
+                   "WS 42 BS 35 S 38 T 33 I 30 Ag 31 Dex 29 Int 27 WP 36 Fel 25 W 12 M 4; Fate 2, Fortune 3, Resilience 1, Resolve 1, Corruption 0; Enc 0/6\n\
+                    Skills: Melee (Basic) 47, Dodge 38; Talents: Very Strong, Strike Mighty Blow 2; Trappings: Hand Weapon, Torch (3)");
+    }
+
+    #[test]
+    fn print_compact_character_without_sections() {
+        let character = Character { talents: vec![], skills: vec![], trappings: vec![], ..character() };
+        assert_eq!(CompactCharacterDisplayer::new().print(&character),
+                   "WS 42 BS 35 S 38 T 33 I 30 Ag 31 Dex 29 Int 27 WP 36 Fel 25 W 12 M 4; Fate 2, Fortune 3, Resilience 1, Resolve 1, Corruption 0; Enc 0/6");
+    }
+
+    #[test]
+    fn print_compact_character_with_status() {
+        let character = Character { status: "Brass 4".parse().ok(), ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).starts_with("Brass 4 — WS 42 "));
+    }
+
+    #[test]
+    fn print_compact_character_with_name() {
+        let character = Character { name: "Greta Schmidt".into(), status: "Silver 1".parse().ok(), ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).starts_with("Greta Schmidt, Silver 1 — WS 42 "));
+    }
+
+    #[test]
+    fn print_compact_character_with_career_history() {
+        let character = Character { name: "Greta Schmidt".into(), status: "Silver 1".parse().ok(), species: "Human".into(),
+                                    career_history: vec![CareerStep { career: "Soldier".into(), level: 2, title: "Soldier".into(), completed: true },
+                                                         CareerStep { career: "Outlaw".into(), level: 1, title: "Brigand".into(), completed: true }],
+                                    ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).starts_with("Greta Schmidt, Silver 1, Human Soldier 2 → Outlaw 1 (Brigand) — WS 42 "));
+        let species_only = Character { species: "Dwarf".into(), ..self::character() };
+        assert!(CompactCharacterDisplayer::new().print(&species_only).starts_with("Dwarf — WS 42 "));
+    }
+
+    #[test]
+    fn print_compact_character_with_affiliation() {
+        let character = Character { name: "Greta Schmidt".into(), affiliation: Some("State Militia".into()), ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).starts_with("Greta Schmidt, State Militia — WS 42 "));
+    }
+
+    #[test]
+    fn print_compact_character_with_details() {
+        let details = Details { age: 84, height: 52, eyes: "Steel".into(), hair: "Copper".into(), birthplace: "Zhufbar".into(), distinguishing_mark: None };
+        let character = Character { details: Some(details), ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).contains("\nDetails: Age 84, Height 4'4\", Eyes Steel, Hair Copper, Born Zhufbar; Skills: "));
+    }
+
+    #[test]
+    fn print_compact_character_with_personality() {
+        let personality = Personality { traits: vec!["Greedy".into()], motivation: "Wealth".into(), short_term_ambition: "Evade the watch".into(),
+                                        long_term_ambition: "Steal from the Emperor".into(), secret: "Is of noble birth".into() };
+        let character = Character { personality: Some(personality), ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).ends_with(
+                "Torch (3)\nRoleplaying: Greedy; Motivation: Wealth; Ambitions: Evade the watch / Steal from the Emperor; Secret: Is of noble birth"));
+    }
+
+    #[test]
+    fn print_compact_character_with_weapons_and_armour() {
+        let mut trappings = character().trappings;
+        trappings[0].weapon = Some(Weapon { name: "Hand Weapon".into(), group: "Basic".into(), damage: "+SB+4".parse().unwrap(), reach: None,
+                                            range: None, qualities: vec![], flaws: vec![], encumbrance: 1 });
+        trappings[1].armour = Some(Armour { name: "Torch".into(), locations: vec![HitLocation::Head], armour_points: 1,
+                                            qualities: vec![], flaws: vec![], encumbrance: 0 });
+        let character = Character { trappings, ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).ends_with("Torch (3); Weapons: Hand Weapon 7; Armour: Head 1, Arms 0, Body 0, Legs 0"));
+    }
+
+    #[test]
+    fn print_compact_character_with_spells() {
+        let spell = |name: &str, cn: Option<u32>| Spell { name: name.into(), cn, range: "You".into(), target: "You".into(), duration: "Instant".into(), effect: "Effect".into() };
+        let character = Character { spells: vec![Lore { name: "Arcane Magic (Fire)".into(), spells: vec![spell("Aqshy's Aegis", Some(5)), spell("Cauterise", Some(4))] },
+                                                 Lore { name: "Invoke (Morr)".into(), spells: vec![spell("Dooming", None)] }],
+                                    ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).ends_with(
+                "Torch (3); Spells (Arcane Magic (Fire)): Aqshy's Aegis CN 5, Cauterise CN 4; Spells (Invoke (Morr)): Dooming"));
+    }
+
+    #[test]
+    fn print_compact_creature() {
+        let character = Character { name: "Giant Rat".into(), talents: vec![], skills: vec![], trappings: vec![],
+                                    traits: vec![CreatureTrait { damage: "+SB+2".parse().ok(), ..CreatureTrait::new("Bite") },
+                                                 CreatureTrait { specialisation: Some("Small".into()), ..CreatureTrait::new("Size") }],
+                                    ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).ends_with("Enc 0/6\nTraits: Bite +5, Size (Small)"));
+    }
+
+    #[test]
+    fn print_compact_small_character() {
+        let character = Character { size: Size::Small, ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).ends_with("Torch (3); Size: Small (+10 to hit Average foes, who deal damage ×2)"));
+    }
+
+    #[test]
+    fn print_compact_overloaded_character() {
+        let character = Character { trappings: vec![Trapping { encumbrance: 4, ..Trapping::new("Anvil", 2) }], ..character() };
+        assert!(CompactCharacterDisplayer::new().print(&character).contains("Corruption 0; Enc 8/6 overloaded\n"));
+    }
+
+    #[test]
+    fn print_compact_character_skips_empty_sections() {
+        let character = Character { skills: vec![], ..character() };
+        assert!(!CompactCharacterDisplayer::new().print(&character).contains("Skills"));
+    }
+}
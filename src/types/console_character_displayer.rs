@@ -1,88 +1,219 @@
-use std::fmt::Display;
 use crate::interfaces::character_displayer::CharacterDisplayer;
-use crate::models::{character::Character, talent::Talent, skill::Skill, trapping::Trapping};
-use crate::types::character_attributes_iter::CharacterAttributesIter;
+use crate::models::{armour::HitLocation, character::Character, size::Size, details::Details, personality::Personality, spell::Lore, talent::Talent};
+use crate::types::character_attributes_iter::{CharacterAttribute, CharacterAttributesIter};
+use crate::types::console_settings::ConsoleSettings;
+use crate::types::console_table::{ConsoleTable, Highlight, TableColumn};
 
-const TABLE_DELIMETER: char = '|';
-const MAX_CONSOLE_DISPLAY_LENGTH: usize = 60;
 const TALENT_NAME: &str         = "Talent        ";
 const TALENT_LEVEL: &str        = "Levels        ";
 const SKILL_NAME: &str          = "Skill         ";
 const SKILL_VALUE: &str         = "Values        ";
 const TRAPPING_NAME: &str       = "Trapping      ";
 const TRAPPING_COUNT: &str      = "Count         ";
+const TRAPPING_ENCUMBRANCE: &str = "Enc           ";
+const TRAPPING_PACKED_IN: &str  = "Packed In     ";
 const GENERAL_SKILL_NAME: &str  = "General Skill ";
 const GENERAL_SKILL_VALUE: &str = "Level         ";
+const POINTS_NAME: &str         = "Points        ";
+const POINTS_VALUE: &str        = "Values        ";
+const DETAIL_NAME: &str         = "Detail        ";
+const DETAIL_VALUE: &str        = "Value         ";
+const WEAPON_NAME: &str         = "Weapon        ";
+const WEAPON_GROUP: &str        = "Group         ";
+const WEAPON_DAMAGE: &str       = "Damage        ";
+const WEAPON_REACH: &str        = "Reach/Range   ";
+const WEAPON_TRAITS: &str       = "Qualities     ";
+const ARMOUR_NAME: &str         = "Armour        ";
+const ARMOUR_COVERS: &str       = "Covers        ";
+const ARMOUR_LOCATION: &str     = "Location      ";
+const ARMOUR_POINTS: &str       = "Armour Points ";
+const ARMOUR_QUALITIES: &str    = "Qualities     ";
+const SPELL_NAME: &str          = "Spell         ";
+const SPELL_CN: &str            = "CN            ";
+const SPELL_RANGE: &str         = "Range         ";
+const SPELL_TARGET: &str        = "Target        ";
+const SPELL_DURATION: &str      = "Duration      ";
+const INITIAL_VALUE: &str       = "Initial       ";
+const ADVANCES_VALUE: &str      = "Advances      ";
 
-macro_rules! create_table {
-    ($iter: ident, $field_top: ident, $field_bottom: ident, $top_prefix: ident, $bottom_prefix: ident) => {{
-        let iter = $iter;
-        let top_row_elements = iter.clone().map(|el| el.$field_top.to_string());
-        let bottom_row_elements = iter.clone().map(|el| el.$field_bottom.to_string());
-
-        let min_top = top_row_elements.clone().max_by_key(|el| el.len()).expect("create_table_failed top_row empty").len();
-        let min_bottom = bottom_row_elements.clone().max_by_key(|el| el.len()).expect("create_table_failed bottom_row empty").len();
-        let min_width = min_bottom.max(min_top); 
-        
-        let elem_count = ((MAX_CONSOLE_DISPLAY_LENGTH - $top_prefix.len().max($bottom_prefix.len()))/ min_width).max(1);
-        
-        let zipped_strings_iter = top_row_elements.zip(bottom_row_elements);
-        let mut peekable_data_iter = zipped_strings_iter.peekable();
-        let peekable_data_iter = &mut peekable_data_iter;
-
-        let mut ret = String::new();
-        while peekable_data_iter.peek().is_some() {
-            let table_chunk = peekable_data_iter.take(elem_count);
-            let (top, bottom) =  table_chunk.fold((
-                                                                format!("{}{}", $top_prefix, TABLE_DELIMETER),
-                                                                format!("{}{}", $bottom_prefix, TABLE_DELIMETER)),
-                                                                |(top, bottom), (top_el,bottom_el)| 
-                                                                (top  + Self::create_table_record(top_el, min_width).as_str(),
-                                                                bottom + Self::create_table_record(bottom_el, min_width).as_str()));
-            ret += format!("{}\n{}\n", top, bottom).as_str();
-            for _ in 0..elem_count {
-                peekable_data_iter.peek();
-            }
-        }
-        ret
-    }
+// Skills used to cast spells and pray for Blessings and Miracles
+const CASTING_SKILLS: [&str; 3] = ["Channelling", "Language (Magick)", "Pray"];
+const NO_CASTING_NUMBER: &str = "—";
+
+const HIGH_CHARACTERISTIC: u32 = 40;
+const LOW_CHARACTERISTIC: u32 = 25;
 
-    };
+pub struct ConsoleCharacterDisplayer {
+    settings: ConsoleSettings,
 }
 
-pub struct ConsoleCharacterDisplayer {}
+impl Default for ConsoleCharacterDisplayer {
+    fn default() -> Self {
+        Self::with_settings(ConsoleSettings::default())
+    }
+}
 
 impl ConsoleCharacterDisplayer {
-    pub fn new() ->Self {
-        ConsoleCharacterDisplayer{}
+    pub fn with_settings(settings: ConsoleSettings) ->Self {
+        ConsoleCharacterDisplayer{settings}
     }
-    fn create_table_record(displayed_data: impl Display, min_width: usize)->String {
-        let displayed_string = displayed_data.to_string(); 
-        let justify_length = min_width - displayed_string.len();
-        let justify_string: String = std::iter::repeat(" ").take(justify_length).collect();
-        
-        format!("{}{}{}", displayed_string, justify_string, TABLE_DELIMETER)
+
+    fn characteristic_highlight(attribute: &CharacterAttribute)->Option<Highlight> {
+        if !attribute.attribute.is_characteristic() {
+            None
+        }
+        else if attribute.current >= HIGH_CHARACTERISTIC {
+            Some(Highlight::High)
+        }
+        else if attribute.current <= LOW_CHARACTERISTIC {
+            Some(Highlight::Low)
+        }
+        else {
+            None
+        }
     }
 
-    fn create_talent_table(data: &Vec<Talent>)->String {
+    // Name, status and career lines, left out for an anonymous NPC
+    fn create_header(character: &Character)->String {
+        const NAME_HEADER: &str = "Name: ";
+        const STATUS_HEADER: &str = "Status: ";
+        const CAREER_HEADER: &str = "Career: ";
+        const SPECIES_HEADER: &str = "Species: ";
+        const AFFILIATION_HEADER: &str = "Affiliation: ";
+        let switch_cost = match character.career_switch_cost() {
+            0 => String::new(),
+            cost => format!(" ({} XP spent changing careers)", cost),
+        };
+        let lines: Vec<String> = [
+            Some(character.name.as_str()).filter(|name| !name.is_empty()).map(|name| format!("{}{}", NAME_HEADER, name)),
+            character.status.map(|status| format!("{}{}", STATUS_HEADER, status)),
+            Some(character.species.as_str()).filter(|species| !species.is_empty()).map(|species| format!("{}{}", SPECIES_HEADER, species)),
+            Some(character.formatted_career_history()).filter(|history| !history.is_empty())
+                                                      .map(|history| format!("{}{}{}", CAREER_HEADER, history, switch_cost)),
+            character.affiliation.as_ref().map(|affiliation| format!("{}{}", AFFILIATION_HEADER, affiliation)),
+        ].into_iter().flatten().collect();
 
-        let data_iter= data.iter();
-        create_table!(data_iter, name, level, TALENT_NAME, TALENT_LEVEL)
+        if lines.is_empty() {
+            String::new()
+        }
+        else {
+            format!("{}\n\n", lines.join("\n"))
+        }
     }
-    fn create_skill_table(data: &Vec<Skill>)->String {
 
-        let data_iter= data.iter();
-        create_table!(data_iter, name, value, SKILL_NAME, SKILL_VALUE)
+    fn create_talent_table(&self, data: &[Talent])->String {
+        ConsoleTable::new(TALENT_NAME, &[TALENT_LEVEL])
+            .with_columns(data.iter().map(|talent| TableColumn::new(&talent.name, talent.level)))
+            .render(&self.settings)
     }
-    fn create_trappings_table(data: &Vec<Trapping>)->String {
-
-        let data_iter= data.iter();
-        create_table!(data_iter, name, count, TRAPPING_NAME, TRAPPING_COUNT)
+    fn create_skill_table(&self, character: &Character)->String {
+        ConsoleTable::new(SKILL_NAME, &[INITIAL_VALUE, ADVANCES_VALUE, SKILL_VALUE])
+            .with_columns(character.skills.iter().map(|skill| {
+                let value = character.skill_value(skill);
+                TableColumn::new(&skill.name, value.initial).with_value(value.advances).with_value(value.current())
+            }))
+            .render(&self.settings)
     }
-    fn create_general_skills_table(character: &Character)->String {
-
-        let data_iter = CharacterAttributesIter::new(character);
-        create_table!(data_iter, name, level, GENERAL_SKILL_NAME, GENERAL_SKILL_VALUE)
+    // The packing row only shows up once something is packed
+    fn create_trappings_table(&self, character: &Character)->String {
+        let packed = character.trappings.iter().any(|trapping| trapping.packed_in.is_some());
+        let value_prefixes: &[&str] = if packed { &[TRAPPING_COUNT, TRAPPING_ENCUMBRANCE, TRAPPING_PACKED_IN] } else { &[TRAPPING_COUNT, TRAPPING_ENCUMBRANCE] };
+        let table = ConsoleTable::new(TRAPPING_NAME, value_prefixes)
+            .with_columns(character.trappings.iter().map(|trapping| {
+                let column = TableColumn::new(&trapping.name, trapping.count).with_value(trapping.total_encumbrance());
+                if packed { column.with_value(trapping.packed_in.as_deref().unwrap_or_default()) } else { column }
+            }))
+            .render(&self.settings);
+        format!("{}\n{}", table, Self::create_encumbrance_line(character))
+    }
+    fn create_encumbrance_line(character: &Character)->String {
+        const ENCUMBRANCE_HEADER: &str = "Encumbrance: ";
+        const OVERLOADED: &str = " — overloaded";
+        format!("{}{} of {}{}\n", ENCUMBRANCE_HEADER, character.total_encumbrance(), character.encumbrance_limit(),
+                if character.is_overloaded() { OVERLOADED } else { "" })
+    }
+    // Weapons with damage against the character's Strength Bonus and armour by hit location, None when unarmed and unarmoured
+    fn create_combat_tables(&self, character: &Character)->Option<String> {
+        if character.weapons().next().is_none() && character.armour().next().is_none() && !character.is_armoured() && character.size == Size::Average {
+            return None;
+        }
+        let weapons = ConsoleTable::new(WEAPON_NAME, &[WEAPON_GROUP, WEAPON_DAMAGE, WEAPON_REACH, WEAPON_TRAITS])
+            .with_columns(character.weapons().map(|weapon| TableColumn::new(&weapon.name, &weapon.group)
+                                                               .with_value(character.weapon_damage(weapon))
+                                                               .with_value(weapon.formatted_reach())
+                                                               .with_value(weapon.traits().join(", "))))
+            .render(&self.settings);
+        let armour = ConsoleTable::new(ARMOUR_NAME, &[ARMOUR_COVERS, ARMOUR_POINTS, ARMOUR_QUALITIES])
+            .with_columns(character.armour().map(|armour| TableColumn::new(&armour.name, armour.formatted_locations())
+                                                              .with_value(armour.armour_points)
+                                                              .with_value(armour.traits().join(", "))))
+            .render(&self.settings);
+        let locations = ConsoleTable::new(ARMOUR_LOCATION, &[ARMOUR_POINTS])
+            .with_columns(HitLocation::iterator().map(|location| TableColumn::new(location.to_str(), character.armour_points(*location))))
+            .render(&self.settings);
+        let size = if character.size == Size::Average { String::new() }
+                   else { format!("\nSize: {} — {}\n", character.size, character.size.combat_modifiers()) };
+        Some(format!("{}\n{}\n{}{}", weapons, armour, locations, size))
+    }
+    // Casting skills, then a table and the effects of every known lore, None for an NPC without magic
+    fn create_spell_tables(&self, character: &Character)->Option<String> {
+        if character.spells.is_empty() {
+            return None;
+        }
+        let casting: Vec<String> = character.skills
+                                            .iter()
+                                            .filter(|skill| CASTING_SKILLS.contains(&skill.name.as_str()))
+                                            .map(|skill| format!("{} {}", skill.name, character.skill_value(skill).current()))
+                                            .collect();
+        let casting_line = if casting.is_empty() { String::new() } else { format!("Casting: {}\n\n", casting.join(", ")) };
+        let lores: Vec<String> = character.spells.iter().map(|lore| self.create_lore_table(lore)).collect();
+        Some(format!("{}{}", casting_line, lores.join("\n")))
+    }
+    fn create_lore_table(&self, lore: &Lore)->String {
+        let table = ConsoleTable::new(SPELL_NAME, &[SPELL_CN, SPELL_RANGE, SPELL_TARGET, SPELL_DURATION])
+            .with_columns(lore.spells.iter().map(|spell| TableColumn::new(&spell.name, spell.cn.map_or(NO_CASTING_NUMBER.to_string(), |cn| cn.to_string()))
+                                                             .with_value(&spell.range)
+                                                             .with_value(&spell.target)
+                                                             .with_value(&spell.duration)))
+            .render(&self.settings);
+        let effects: String = lore.spells.iter().map(|spell| format!("{}: {}\n", spell.name, spell.effect)).collect();
+        format!("{}\n\n{}\n{}", lore.name, table, effects)
+    }
+    fn create_points_table(&self, character: &Character)->String {
+        let points = [("Fate", character.fate), ("Fortune", character.fortune), ("Resilience", character.resilience),
+                      ("Resolve", character.resolve), ("Corruption", character.corruption)];
+        ConsoleTable::new(POINTS_NAME, &[POINTS_VALUE])
+            .with_columns(points.into_iter().map(|(name, value)| TableColumn::new(name, value)))
+            .render(&self.settings)
+    }
+    fn create_details_table(&self, details: &Details)->String {
+        let mut data = vec![("Age", details.age.to_string()), ("Height", details.formatted_height()), ("Eyes", details.eyes.clone()),
+                            ("Hair", details.hair.clone()), ("Birthplace", details.birthplace.clone())];
+        data.extend(details.distinguishing_mark.iter().map(|mark| ("Mark", mark.clone())));
+        ConsoleTable::new(DETAIL_NAME, &[DETAIL_VALUE])
+            .with_columns(data.into_iter().map(|(name, value)| TableColumn::new(name, value)))
+            .render(&self.settings)
+    }
+    // Prose does not fit table columns, so every hook gets its own line
+    fn create_personality_lines(personality: &Personality)->String {
+        format!("Personality: {}\nMotivation: {}\nShort-term Ambition: {}\nLong-term Ambition: {}\nSecret: {}\n",
+                personality.traits.join(", "), personality.motivation, personality.short_term_ambition, personality.long_term_ambition,
+                personality.secret)
+    }
+    // Traits are prose as well, one per line with what it does
+    fn create_trait_lines(character: &Character)->String {
+        character.traits
+                 .iter()
+                 .map(|creature_trait| format!("{}: {}\n", character.formatted_trait(creature_trait), creature_trait.description))
+                 .collect()
+    }
+    fn create_general_skills_table(&self, character: &Character)->String {
+        ConsoleTable::new(GENERAL_SKILL_NAME, &[INITIAL_VALUE, ADVANCES_VALUE, GENERAL_SKILL_VALUE])
+            .with_columns(CharacterAttributesIter::new(character).map(|attribute| TableColumn::new(attribute.name, attribute.initial)
+                                                                                     .with_value(attribute.advances)
+                                                                                     .with_value(attribute.current)
+                                                                                     .highlighted(Self::characteristic_highlight(&attribute))))
+            .render(&self.settings)
     }
 }
 
@@ -92,53 +223,262 @@ impl CharacterDisplayer<String> for ConsoleCharacterDisplayer {
         const SKILLS_HEADER: &str = "---Skills---";
         const TRAPPINGS_HEADER: &str = "---Trappings---";
         const GENERAL_SKILLS_HEADER: &str = "---General Skills---";
-        format!("{}\n\n{}\n{}\n\n{}\n{}\n\n{}\n{}\n\n{}",
-                TALENTS_HEADER,
-                Self::create_talent_table(&character.talents),
-                SKILLS_HEADER,
-                Self::create_skill_table(&character.skills),
-                TRAPPINGS_HEADER,
-                Self::create_trappings_table(&character.trappings),
-                GENERAL_SKILLS_HEADER,
-                Self::create_general_skills_table(&character))
+        const POINTS_HEADER: &str = "---Fate & Resilience---";
+        const DETAILS_HEADER: &str = "---Details---";
+        const ROLEPLAYING_HEADER: &str = "---Roleplaying---";
+        const COMBAT_HEADER: &str = "---Combat---";
+        const SPELLS_HEADER: &str = "---Spells---";
+        const TRAITS_HEADER: &str = "---Traits---";
+        // creatures rarely have talents, skills or trappings, so empty tables are left out for them
+        let shown = |empty: bool| !(empty && character.is_creature());
+        let sections: Vec<(&str, String)> = [
+            character.details.as_ref().map(|details| (DETAILS_HEADER, self.create_details_table(details))),
+            Some((TRAITS_HEADER, Self::create_trait_lines(character))).filter(|_| character.is_creature()),
+            Some((TALENTS_HEADER, self.create_talent_table(&character.talents))).filter(|_| shown(character.talents.is_empty())),
+            Some((SKILLS_HEADER, self.create_skill_table(character))).filter(|_| shown(character.skills.is_empty())),
+            Some((TRAPPINGS_HEADER, self.create_trappings_table(character))).filter(|_| shown(character.trappings.is_empty())),
+            self.create_combat_tables(character).map(|tables| (COMBAT_HEADER, tables)),
+            self.create_spell_tables(character).map(|tables| (SPELLS_HEADER, tables)),
+            Some((GENERAL_SKILLS_HEADER, self.create_general_skills_table(character))),
+            Some((POINTS_HEADER, self.create_points_table(character))),
+            character.personality.as_ref().map(|personality| (ROLEPLAYING_HEADER, Self::create_personality_lines(personality))),
+        ].into_iter().flatten().collect();
+
+        format!("{}{}",
+                Self::create_header(character),
+                sections.iter().map(|(header, table)| format!("{}\n\n{}", header, table)).collect::<Vec<String>>().join("\n"))
     }
 }
 
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use crate::interfaces::resource_bag::ResourceBag;
+    use crate::models::{base_attribute::BaseAttribute, career_step::CareerStep, creature_trait::CreatureTrait, profession::Profession, skill::Skill, spell::Spell, stat_value::StatValue, trapping::Trapping};
+    use crate::types::game_data::GameData;
 
     #[test] 
-    fn create_table_record_test() {
-        assert_eq!(ConsoleCharacterDisplayer::create_table_record(1,2), "1 |") 
+    fn create_talents_table_test() {
+        let talents = vec![Talent{name: "Tal".into(), level: 1, max_level: 3, description: "Desc".into(), magic: None, specialisations: vec![]}];
+        assert_eq!(ConsoleCharacterDisplayer::default().create_talent_table(&talents), "Talent        |Tal|\nLevels        |1  |\n") 
     }
     #[test] 
-    fn create_talents_table_test() {
-        let talents = vec![Talent{name: "Tal".into(), level: 1, max_level: 3, description: "Desc".into()}];
-        assert_eq!(ConsoleCharacterDisplayer::create_talent_table(&talents), "Talent        |Tal|\nLevels        |1  |\n") 
+    fn create_empty_talents_table_test() {
+        assert_eq!(ConsoleCharacterDisplayer::default().create_talent_table(&[]), "— none —\n") 
+    }
+    #[test] 
+    fn characteristic_highlight_test() {
+        let character = Character { weapon_skill: StatValue{initial: 40, advances: 5}, ballistic_skill: StatValue::new(20), strength: StatValue::new(30), wounds: 2, ..character() };
+        let highlights: Vec<Option<Highlight>> = CharacterAttributesIter::new(&character).map(|attribute| ConsoleCharacterDisplayer::characteristic_highlight(&attribute)).collect();
+        assert_eq!(highlights[0..3], [Some(Highlight::High), Some(Highlight::Low), None]);
+        assert_eq!(highlights[10], None);
+    }
+    fn character() -> Character {
+        Character {
+            talents: vec![Talent{name: "OtherTalent1".into(), level: 1, max_level: 2, description: "Desc".into(), magic: None, specialisations: vec![]},
+                          Talent{name: "Talent31".into(), level: 1, max_level: 2, description: "Desc".into(), magic: None, specialisations: vec![]},],
+            skills: vec![Skill{name: "Skill1".into(), advances: 31, base_attribute: BaseAttribute::Agility},
+                         Skill{name: "OtherSkill2".into(), advances: 30, base_attribute: BaseAttribute::BallisticSkill},],
+            trappings: vec![Trapping::new("Tropp1", 2), Trapping::new("Tropp2", 2),],
+            ballistic_skill: StatValue{initial: 1, advances: 1},
+            weapon_skill: StatValue::new(1),
+            strength: StatValue::new(1),
+            toughness: StatValue::new(1),
+            initiative: StatValue::new(1),
+            agility: StatValue::new(1),
+            dexterity: StatValue::new(2),
+            intelligence: StatValue::new(1),
+            willpower: StatValue::new(3),
+            fellowship: StatValue::new(3),
+            wounds: 2,
+            movement: 1,
+            size: Size::Average,
+            fate: 3,
+            fortune: 4,
+            resilience: 1,
+            resolve: 1,
+            corruption: 0,
+            status: None,
+            career_history: vec![],
+            affiliation: None,
+            name: String::new(),
+            species: String::new(),
+            details: None,
+            personality: None,
+            spells: vec![],
+            traits: vec![],
+        }
+    }
+    #[test]
+    fn print_character_without_talents_and_trappings() {
+        let character = Character { talents: vec![], trappings: vec![], ..character() };
+        let printed = ConsoleCharacterDisplayer::default().print(&character);
+        assert!(printed.starts_with("---Talents---\n\n— none —\n"));
+        assert!(printed.contains("---Trappings---\n\n— none —\n"));
+    }
+    #[test]
+    fn print_character_with_status() {
+        let character = Character { status: "Silver 3".parse().ok(), ..character() };
+        assert!(ConsoleCharacterDisplayer::default().print(&character).starts_with("Status: Silver 3\n\n---Talents---\n"));
+    }
+    #[test]
+    fn print_character_with_name() {
+        let character = Character { name: "Grimnir Ironbeard".into(), status: "Brass 2".parse().ok(), ..character() };
+        assert!(ConsoleCharacterDisplayer::default().print(&character).starts_with("Name: Grimnir Ironbeard\nStatus: Brass 2\n\n---Talents---\n"));
+    }
+    #[test]
+    fn print_character_with_affiliation() {
+        let character = Character { status: "Brass 1".parse().ok(), affiliation: Some("Reikwald Bandits".into()), ..character() };
+        assert!(ConsoleCharacterDisplayer::default().print(&character).starts_with("Status: Brass 1\nAffiliation: Reikwald Bandits\n\n"));
+    }
+
+    #[test]
+    fn print_character_with_career_history() {
+        let step = |career: &str, level: u8, title: &str| CareerStep { career: career.into(), level, title: title.into(), completed: true };
+        let character = Character { status: "Brass 2".parse().ok(), species: "Human".into(), career_history: vec![step("Soldier", 3, "Sergeant"), step("Outlaw", 2, "Outlaw")],
+                                    ..character() };
+        assert!(ConsoleCharacterDisplayer::default().print(&character)
+                    .starts_with("Status: Brass 2\nSpecies: Human\nCareer: Soldier 3 (Sergeant) → Outlaw 2 (100 XP spent changing careers)\n\n---Talents---\n"));
+    }
+    #[test]
+    fn print_character_with_details() {
+        let details = Details { age: 31, height: 69, eyes: "Grey".into(), hair: "Auburn".into(), birthplace: "Übersreik".into(), distinguishing_mark: Some("Tattoo".into()) };
+        let character = Character { details: Some(details), ..character() };
+        let printed = ConsoleCharacterDisplayer::with_settings(ConsoleSettings { width: 120, ..ConsoleSettings::default() }).print(&character);
+        assert_eq!(printed.split("---Skills---").next().unwrap(),
+"---Details---
+
+Detail        |Age       |Height    |Eyes      |Hair      |Birthplace|Mark      |
+Value         |31        |5'9\"      |Grey      |Auburn    |Übersreik |Tattoo    |
+
+---Talents---
+
+Talent        |OtherTalent1|Talent31    |
+Levels        |1           |1           |
+
+");
+    }
+    #[test]
+    fn print_character_with_personality() {
+        let personality = Personality { traits: vec!["Gruff".into(), "Loyal".into()], motivation: "Duty".into(), short_term_ambition: "Be promoted".into(),
+                                        long_term_ambition: "Be knighted".into(), secret: "Sold army supplies".into() };
+        let character = Character { personality: Some(personality), ..character() };
+        assert!(ConsoleCharacterDisplayer::default().print(&character).ends_with("Values        |0         |
+
+---Roleplaying---
+
+Personality: Gruff, Loyal
+Motivation: Duty
+Short-term Ambition: Be promoted
+Long-term Ambition: Be knighted
+Secret: Sold army supplies
+"));
+    }
+    #[test]
+    fn print_character_with_combat() {
+        let profession: Profession = GameData::embedded().get_by_id("soldier_2").unwrap();
+        let trappings = profession.class.trappings
+                                  .into_iter()
+                                  .chain(profession.trappings)
+                                  .filter(|trapping| ["Hand Weapon", "Helmet"].contains(&trapping.name.as_str()))
+                                  .collect();
+        let character = Character { strength: StatValue::new(31), trappings, ..character() };
+        let printed = ConsoleCharacterDisplayer::with_settings(ConsoleSettings { width: 80, ..ConsoleSettings::default() }).print(&character);
+        assert!(printed.contains("---Combat---
+
+Weapon        |Hand Weapon|
+Group         |Basic      |
+Damage        |7          |
+Reach/Range   |Average    |
+Qualities     |           |
+
+Armour        |Helmet |
+Covers        |Head   |
+Armour Points |2      |
+Qualities     |Partial|
+
+Location      |Head|Arms|Body|Legs|
+Armour Points |2   |0   |0   |0   |
+"), "{}", printed);
+    }
+    #[test]
+    fn print_character_with_spells() {
+        let spells = Lore { name: "Petty Magic".into(), spells: vec![Spell { name: "Dart".into(), cn: Some(0), range: "Willpower yards".into(), target: "1".into(),
+                                                                              duration: "Instant".into(), effect: "Magic Missile with +0 Damage.".into() }] };
+        let blessings = Lore { name: "Bless (Sigmar)".into(), spells: vec![Spell { name: "Blessing of Battle".into(), cn: None, range: "6 yards".into(),
+                                                                                  target: "1".into(), duration: "6 rounds".into(), effect: "+10 to Weapon Skill.".into() }] };
+        let skills = vec![Skill { name: "Channelling".into(), advances: 5, base_attribute: BaseAttribute::Willpower }];
+        let character = Character { spells: vec![spells, blessings], skills, ..character() };
+        let printed = ConsoleCharacterDisplayer::with_settings(ConsoleSettings { width: 80, ..ConsoleSettings::default() }).print(&character);
+        assert!(printed.contains("---Spells---
+
+Casting: Channelling 8
+
+Petty Magic
+
+Spell         |Dart           |
+CN            |0              |
+Range         |Willpower yards|
+Target        |1              |
+Duration      |Instant        |
+
+Dart: Magic Missile with +0 Damage.
+
+Bless (Sigmar)
+
+Spell         |Blessing of Battle|
+CN            |—                 |
+Range         |6 yards           |
+Target        |1                 |
+Duration      |6 rounds          |
+
+Blessing of Battle: +10 to Weapon Skill.
+
+---General Skills---"), "{}", printed);
+    }
+    #[test]
+    fn print_creature() {
+        let bite = CreatureTrait { damage: "+SB+3".parse().ok(), description: "Free bite attack.".into(), ..CreatureTrait::new("Bite") };
+        let character = Character { name: "Wolf".into(), strength: StatValue::new(31), talents: vec![], skills: vec![], trappings: vec![],
+                                    traits: vec![bite, CreatureTrait { rating: Some(1), description: "Thick hide.".into(), ..CreatureTrait::new("Armour") }],
+                                    ..character() };
+        let printed = ConsoleCharacterDisplayer::default().print(&character);
+        assert!(printed.starts_with("Name: Wolf
+
+---Traits---
+
+Bite +6: Free bite attack.
+Armour 1: Thick hide.
+
+---Combat---"), "{}", printed);
+        assert!(printed.contains("Location      |Head|Arms|Body|Legs|\nArmour Points |1   |1   |1   |1   |\n"), "{}", printed);
+    }
+    #[test]
+    fn print_size_combat_modifiers() {
+        let character = Character { size: Size::Large, ..character() };
+        let printed = ConsoleCharacterDisplayer::default().print(&character);
+        assert!(printed.contains("Armour Points |0   |0   |0   |0   |\n\nSize: Large — damage ×2 against Average foes, who gain +10 to hit\n"), "{}", printed);
+    }
+    #[test]
+    fn print_overloaded_character_with_packing() {
+        let trappings = vec![Trapping { carries: 2, encumbrance: 1, ..Trapping::new("Sling Bag", 1) },
+                             Trapping { encumbrance: 1, packed_in: Some("Sling Bag".into()), ..Trapping::new("Rope", 2) }];
+        let character = Character { strength: StatValue::new(10), toughness: StatValue::new(10), trappings, ..character() };
+        let printed = ConsoleCharacterDisplayer::default().print(&character);
+        assert!(printed.contains("---Trappings---
+
+Trapping      |Sling Bag|Rope     |
+Count         |1        |2        |
+Enc           |1        |2        |
+Packed In     |         |Sling Bag|
+
+Encumbrance: 3 of 2 — overloaded
+"), "{}", printed);
     }
     #[test]
     fn print_character() {
-        let character = Character {
-            talents: vec![Talent{name: "OtherTalent1".into(), level: 1, max_level: 2, description: "Desc".into(),},
-                          Talent{name: "Talent31".into(), level: 1, max_level: 2, description: "Desc".into()},],
-            skills: vec![Skill{name: "Skill1".into(), value: 32, base_attribute: crate::models::base_attribute::BaseAttribute::Agility},
-                         Skill{name: "OtherSkill2".into(), value: 32, base_attribute: crate::models::base_attribute::BaseAttribute::BallisticSkill},],
-            trappings: vec![Trapping{name: "Tropp1".into(), count: 2}, Trapping{name: "Tropp2".into(), count: 2},],
-            ballistic_skill: 1,
-            weapon_skill: 1,
-            strength: 1,
-            toughness: 1,
-            initiative: 1,
-            agility: 1,
-            dexterity: 2,
-            intelligence: 1,
-            willpower: 3,
-            fellowship: 3,
-            wounds: 2,
-            movement: 1
-        };
-        assert_eq!(ConsoleCharacterDisplayer::new().print(&character), 
+        let character = character();
+        assert_eq!(ConsoleCharacterDisplayer::default().print(&character), 
         "---Talents---
 
 Talent        |OtherTalent1|Talent31    |
@@ -147,23 +487,43 @@ Levels        |1           |1           |
 ---Skills---
 
 Skill         |Skill1     |OtherSkill2|
+Initial       |1          |2          |
+Advances      |31         |30         |
 Values        |32         |32         |
 
 ---Trappings---
 
 Trapping      |Tropp1|Tropp2|
 Count         |2     |2     |
+Enc           |0     |0     |
+
+Encumbrance: 0 of 0
 
 ---General Skills---
 
 General Skill |Weapon Skill   |Ballistic Skill|Strength       |
-Level         |1              |1              |1              |
+Initial       |1              |1              |1              |
+Advances      |0              |1              |0              |
+Level         |1              |2              |1              |
 General Skill |Toughness      |Initiative     |Agility        |
+Initial       |1              |1              |1              |
+Advances      |0              |0              |0              |
 Level         |1              |1              |1              |
 General Skill |Dexterity      |Intelligence   |Willpower      |
+Initial       |2              |1              |3              |
+Advances      |0              |0              |0              |
 Level         |2              |1              |3              |
 General Skill |FellowShip     |Wounds         |Movement       |
+Initial       |3              |2              |1              |
+Advances      |0              |0              |0              |
 Level         |3              |2              |1              |
+
+---Fate & Resilience---
+
+Points        |Fate      |Fortune   |Resilience|Resolve   |
+Values        |3         |4         |1         |1         |
+Points        |Corruption|
+Values        |0         |
 "               )
     }
 }
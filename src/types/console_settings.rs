@@ -0,0 +1,59 @@
+use std::io::IsTerminal;
+
+use super::command_option::{CommandOption, CommandOptions};
+
+pub const DEFAULT_CONSOLE_DISPLAY_LENGTH: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderStyle {
+    #[default]
+    Ascii,
+    Unicode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsoleSettings {
+    pub width: usize,
+    pub border: BorderStyle,
+    pub color: bool,
+}
+
+impl Default for ConsoleSettings {
+    fn default() -> Self {
+        ConsoleSettings {
+            width: DEFAULT_CONSOLE_DISPLAY_LENGTH,
+            border: BorderStyle::Ascii,
+            color: false,
+        }
+    }
+}
+
+impl ConsoleSettings {
+    // Explicit options win, otherwise the terminal is asked; anything that is not a TTY gets plain output
+    pub fn from_options(options: &CommandOptions) -> Self {
+        let is_terminal = std::io::stdout().is_terminal();
+        let width = options.value(CommandOption::Width)
+                           .and_then(|width| width.parse().ok())
+                           .or_else(|| terminal_size::terminal_size().map(|(width, _)| width.0 as usize))
+                           .unwrap_or(DEFAULT_CONSOLE_DISPLAY_LENGTH);
+        let border = if options.is_set(CommandOption::Unicode) { BorderStyle::Unicode } else { BorderStyle::Ascii };
+
+        ConsoleSettings {
+            width,
+            border,
+            color: is_terminal && !options.is_set(CommandOption::NoColor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn settings_from_options() {
+        let args: Vec<String> = vec!["--width".into(), "100".into(), "--unicode".into(), "--no-color".into()];
+        assert_eq!(ConsoleSettings::from_options(&CommandOptions(&args)),
+                   ConsoleSettings{width: 100, border: BorderStyle::Unicode, color: false});
+    }
+}
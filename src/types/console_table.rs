@@ -0,0 +1,249 @@
+use std::fmt::Display;
+
+use unicode_width::UnicodeWidthStr;
+
+use super::console_settings::{BorderStyle, ConsoleSettings};
+
+const TABLE_DELIMETER: char = '|';
+const BOX_VERTICAL: char = '│';
+const BOX_HORIZONTAL: char = '─';
+const BOX_TOP: (char, char, char) = ('┌', '┬', '┐');
+const BOX_MIDDLE: (char, char, char) = ('├', '┼', '┤');
+const BOX_BOTTOM: (char, char, char) = ('└', '┴', '┘');
+pub const EMPTY_TABLE: &str = "— none —";
+
+const ANSI_HIGH: &str = "\x1b[32m";
+const ANSI_LOW: &str = "\x1b[31m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    High,
+    Low,
+}
+
+impl Highlight {
+    fn ansi_code(self) -> &'static str {
+        match self {
+            Highlight::High => ANSI_HIGH,
+            Highlight::Low => ANSI_LOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableCell {
+    pub text: String,
+    pub highlight: Option<Highlight>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableColumn {
+    pub header: String,
+    pub cells: Vec<TableCell>,
+}
+
+impl TableColumn {
+    pub fn new(header: impl Display, value: impl Display) -> Self {
+        TableColumn {
+            header: header.to_string(),
+            cells: vec![],
+        }.with_value(value)
+    }
+
+    pub fn with_value(mut self, value: impl Display) -> Self {
+        self.cells.push(TableCell { text: value.to_string(), highlight: None });
+        self
+    }
+
+    // Highlights the most recently added value
+    pub fn highlighted(mut self, highlight: Option<Highlight>) -> Self {
+        if let Some(cell) = self.cells.last_mut() {
+            cell.highlight = highlight;
+        }
+        self
+    }
+
+    fn width(&self) -> usize {
+        self.cells.iter().map(|cell| cell.text.width()).fold(self.header.width(), usize::max)
+    }
+}
+
+// Table with a header row and one or more value rows, which wraps its columns into chunks fitting the console width
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleTable {
+    header_prefix: String,
+    value_prefixes: Vec<String>,
+    columns: Vec<TableColumn>,
+}
+
+impl ConsoleTable {
+    pub fn new(header_prefix: &str, value_prefixes: &[&str]) -> Self {
+        ConsoleTable {
+            header_prefix: header_prefix.to_string(),
+            value_prefixes: value_prefixes.iter().map(|prefix| prefix.to_string()).collect(),
+            columns: vec![],
+        }
+    }
+
+    pub fn with_columns(mut self, columns: impl IntoIterator<Item = TableColumn>) -> Self {
+        self.columns.extend(columns);
+        self
+    }
+
+    pub fn render(&self, settings: &ConsoleSettings) -> String {
+        let Some(column_width) = self.columns.iter().map(TableColumn::width).max() else {
+            return format!("{}\n", EMPTY_TABLE);
+        };
+        let prefix_width = self.value_prefixes.iter().map(|prefix| prefix.width()).fold(self.header_prefix.width(), usize::max);
+        let columns_per_chunk = (settings.width.saturating_sub(prefix_width) / column_width.max(1)).max(1);
+
+        self.columns
+            .chunks(columns_per_chunk)
+            .map(|chunk| self.render_chunk(chunk, prefix_width, column_width, settings))
+            .collect()
+    }
+
+    fn render_chunk(&self, chunk: &[TableColumn], prefix_width: usize, column_width: usize, settings: &ConsoleSettings) -> String {
+        let (delimeter, left_border) = match settings.border {
+            BorderStyle::Ascii => (TABLE_DELIMETER, String::new()),
+            BorderStyle::Unicode => (BOX_VERTICAL, BOX_VERTICAL.to_string()),
+        };
+        let header_row: String = chunk.iter()
+                                      .map(|column| Self::create_cell(&column.header, column_width, None, delimeter))
+                                      .collect();
+        let header_row = format!("{}{}{}{}\n", left_border, Self::pad(&self.header_prefix, prefix_width), delimeter, header_row);
+        let value_rows: String = self.value_prefixes
+                                     .iter()
+                                     .enumerate()
+                                     .map(|(row, prefix)| {
+                                         let cells: String = chunk.iter()
+                                                                  .map(|column| match column.cells.get(row) {
+                                                                      Some(cell) => Self::create_cell(&cell.text, column_width, cell.highlight.filter(|_| settings.color), delimeter),
+                                                                      None => Self::create_cell("", column_width, None, delimeter),
+                                                                  })
+                                                                  .collect();
+                                         format!("{}{}{}{}\n", left_border, Self::pad(prefix, prefix_width), delimeter, cells)
+                                     })
+                                     .collect();
+
+        match settings.border {
+            BorderStyle::Ascii => format!("{}{}", header_row, value_rows),
+            BorderStyle::Unicode => format!("{}{}{}{}{}",
+                                            Self::create_border_line(prefix_width, column_width, chunk.len(), BOX_TOP),
+                                            header_row,
+                                            Self::create_border_line(prefix_width, column_width, chunk.len(), BOX_MIDDLE),
+                                            value_rows,
+                                            Self::create_border_line(prefix_width, column_width, chunk.len(), BOX_BOTTOM)),
+        }
+    }
+
+    fn create_cell(text: &str, width: usize, highlight: Option<Highlight>, delimeter: char) -> String {
+        let padding = " ".repeat(width.saturating_sub(text.width()));
+        match highlight {
+            Some(highlight) => format!("{}{}{}{}{}", highlight.ansi_code(), text, ANSI_RESET, padding, delimeter),
+            None => format!("{}{}{}", text, padding, delimeter),
+        }
+    }
+
+    fn pad(text: &str, width: usize) -> String {
+        format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+    }
+
+    // Horizontal rule for unicode tables, e.g. ┌────┬──┐
+    fn create_border_line(prefix_width: usize, column_width: usize, columns: usize, (left, middle, right): (char, char, char)) -> String {
+        let column_line: String = std::iter::repeat_n(BOX_HORIZONTAL, column_width).collect();
+        let columns_line: String = std::iter::repeat_n(format!("{}{}", middle, column_line), columns).collect();
+        let prefix_line: String = std::iter::repeat_n(BOX_HORIZONTAL, prefix_width).collect();
+
+        format!("{}{}{}{}\n", left, prefix_line, columns_line, right)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn table() -> ConsoleTable {
+        ConsoleTable::new("Name ", &["Value"])
+            .with_columns(vec![TableColumn::new("Tal", 1), TableColumn::new("Ent", 22)])
+    }
+
+    #[test]
+    fn render_ascii() {
+        assert_eq!(table().render(&ConsoleSettings::default()), "Name |Tal|Ent|\nValue|1  |22 |\n");
+    }
+
+    #[test]
+    fn render_unicode() {
+        let settings = ConsoleSettings{border: BorderStyle::Unicode, ..ConsoleSettings::default()};
+        assert_eq!(table().render(&settings),
+"┌─────┬───┬───┐
+│Name │Tal│Ent│
+├─────┼───┼───┤
+│Value│1  │22 │
+└─────┴───┴───┘
+");
+    }
+
+    #[test]
+    fn render_wraps_to_width() {
+        let settings = ConsoleSettings{width: 8, ..ConsoleSettings::default()};
+        assert_eq!(table().render(&settings), "Name |Tal|\nValue|1  |\nName |Ent|\nValue|22 |\n");
+    }
+
+    #[test]
+    fn render_narrower_than_prefix() {
+        let settings = ConsoleSettings{width: 1, ..ConsoleSettings::default()};
+        assert_eq!(table().render(&settings), "Name |Tal|\nValue|1  |\nName |Ent|\nValue|22 |\n");
+    }
+
+    #[test]
+    fn render_measures_display_width() {
+        let table = ConsoleTable::new("Place", &["Count"])
+            .with_columns(vec![TableColumn::new("Übersreik", 1), TableColumn::new("Altdorf", 2)]);
+        assert_eq!(table.render(&ConsoleSettings::default()), "Place|Übersreik|Altdorf  |\nCount|1        |2        |\n");
+    }
+
+    #[test]
+    fn render_wide_characters() {
+        let table = ConsoleTable::new("A", &["B"]).with_columns(vec![TableColumn::new("龍", "x")]);
+        assert_eq!(table.render(&ConsoleSettings::default()), "A|龍|\nB|x |\n");
+    }
+
+    #[test]
+    fn render_multiple_value_rows() {
+        let table = ConsoleTable::new("Stat", &["Initial", "Advances", "Current"])
+            .with_columns(vec![TableColumn::new("WS", 31).with_value(5).with_value(36), TableColumn::new("BS", 28)]);
+        assert_eq!(table.render(&ConsoleSettings::default()),
+                   "Stat    |WS|BS|\nInitial |31|28|\nAdvances|5 |  |\nCurrent |36|  |\n");
+    }
+
+    #[test]
+    fn render_multiple_value_rows_unicode() {
+        let settings = ConsoleSettings{border: BorderStyle::Unicode, ..ConsoleSettings::default()};
+        let table = ConsoleTable::new("Stat", &["Initial", "Current"])
+            .with_columns(vec![TableColumn::new("WS", 31).with_value(36)]);
+        assert_eq!(table.render(&settings),
+"┌───────┬──┐
+│Stat   │WS│
+├───────┼──┤
+│Initial│31│
+│Current│36│
+└───────┴──┘
+");
+    }
+
+    #[test]
+    fn render_empty() {
+        assert_eq!(ConsoleTable::new("Talent", &["Levels"]).render(&ConsoleSettings::default()), "— none —\n");
+    }
+
+    #[test]
+    fn render_highlight_only_with_color() {
+        let table = ConsoleTable::new("A", &["B"]).with_columns(vec![TableColumn::new("WS", 45).highlighted(Some(Highlight::High))]);
+        let settings = ConsoleSettings{color: true, ..ConsoleSettings::default()};
+        assert_eq!(table.render(&settings), "A|WS|\nB|\x1b[32m45\x1b[0m|\n");
+        assert_eq!(table.render(&ConsoleSettings::default()), "A|WS|\nB|45|\n");
+    }
+}
@@ -0,0 +1,11 @@
+use std::path::PathBuf;
+
+const APP_DIRECTORY: &str = "wfrp-npc-generator";
+
+// Where the library and the campaign are kept by default: $XDG_DATA_HOME, or ~/.local/share where it is not set
+pub fn data_directory() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(|data_home| data_home.join(APP_DIRECTORY))
+}
@@ -0,0 +1,63 @@
+use std::slice::Iter;
+
+use crate::interfaces::character_displayer::CharacterDisplayer;
+use super::command::CommandError;
+use super::compact_character_displayer::CompactCharacterDisplayer;
+use super::console_character_displayer::ConsoleCharacterDisplayer;
+use super::console_settings::ConsoleSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayFormat {
+    #[default]
+    Table,
+    Compact,
+}
+
+impl DisplayFormat {
+    pub fn iterator() -> Iter<'static, DisplayFormat> {
+        const FORMATS: [DisplayFormat; 2] = [DisplayFormat::Table, DisplayFormat::Compact];
+        FORMATS.iter()
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            DisplayFormat::Table => "table",
+            DisplayFormat::Compact => "compact",
+        }
+    }
+
+    pub fn from_str(format: &str) -> Result<Self, CommandError> {
+        DisplayFormat::iterator()
+            .find(|format_enum| format_enum.to_str() == format)
+            .copied()
+            .ok_or(CommandError::InvalidArguments)
+    }
+
+    pub fn formatted_list() -> String {
+        DisplayFormat::iterator().map(|format| format.to_str()).collect::<Vec<&str>>().join("|")
+    }
+
+    pub fn displayer(self, settings: ConsoleSettings) -> Box<dyn CharacterDisplayer<String>> {
+        match self {
+            DisplayFormat::Table => Box::new(ConsoleCharacterDisplayer::with_settings(settings)),
+            DisplayFormat::Compact => Box::new(CompactCharacterDisplayer::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_format_from_str() {
+        assert_eq!(DisplayFormat::from_str("compact"), Ok(DisplayFormat::Compact));
+        assert_eq!(DisplayFormat::from_str("table"), Ok(DisplayFormat::Table));
+        assert_eq!(DisplayFormat::from_str("yaml"), Err(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn display_format_list() {
+        assert_eq!(DisplayFormat::formatted_list(), "table|compact");
+    }
+}
@@ -0,0 +1,72 @@
+use crate::interfaces::character_displayer::CharacterDisplayer;
+use crate::models::encounter::{Encounter, GroupMember};
+
+const ROSTER_INDENT: &str = "  ";
+
+// Whole encounter as one document, a roster of the group followed by every member in the chosen format
+pub struct EncounterDisplayer<'a> {
+    displayer: &'a dyn CharacterDisplayer<String>,
+}
+
+impl<'a> EncounterDisplayer<'a> {
+    pub fn new(displayer: &'a dyn CharacterDisplayer<String>) -> Self {
+        EncounterDisplayer { displayer }
+    }
+
+    pub fn print(&self, encounter: &Encounter, members: &[GroupMember]) -> String {
+        let sheets = members.iter()
+                            .enumerate()
+                            .map(|(index, member)| format!("--- {}/{} ---\n{}", index + 1, members.len(), self.displayer.print(&member.character)));
+        [Self::create_title(encounter), Self::create_roster(members)].into_iter()
+                                                                    .chain(sheets)
+                                                                    .collect::<Vec<String>>()
+                                                                    .join("\n\n")
+    }
+
+    fn create_title(encounter: &Encounter) -> String {
+        match &encounter.affiliation {
+            Some(affiliation) => format!("=== {} — {} ===", encounter.name, affiliation),
+            None => format!("=== {} ===", encounter.name),
+        }
+    }
+
+    // One line per member, e.g. "Leader: Otto Krieger, Outlaw 3 (Outlaw Chief), W 14"
+    fn create_roster(members: &[GroupMember]) -> String {
+        let lines = members.iter().map(|member| {
+            let character = &member.character;
+            let summary = [character.name.clone(), character.formatted_career_history(), format!("W {}", character.wounds)]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<String>>()
+                .join(", ");
+            match &member.role {
+                Some(role) => format!("{}{}: {}", ROSTER_INDENT, role, summary),
+                None => format!("{}{}", ROSTER_INDENT, summary),
+            }
+        });
+        std::iter::once(format!("Roster ({}):", members.len())).chain(lines).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::{career_step::CareerStep, character::Character};
+    use crate::types::compact_character_displayer::CompactCharacterDisplayer;
+
+    fn member(role: Option<&str>, name: &str, wounds: u32) -> GroupMember {
+        let career_history = vec![CareerStep { career: "Outlaw".into(), level: 1, title: "Brigand".into(), completed: true }];
+        GroupMember { role: role.map(String::from), character: Character { name: name.into(), wounds, career_history, ..Character::default() } }
+    }
+
+    #[test]
+    fn print_roster_then_members() {
+        let encounter = Encounter { name: "Bandit Gang".into(), species: "human".into(), affiliation: Some("Reikwald Bandits".into()), shared_surname: false, members: vec![] };
+        let members = [member(Some("Leader"), "Otto Krieger", 14), member(None, "Hans Brenner", 12)];
+        let displayer = CompactCharacterDisplayer::new();
+        let document = EncounterDisplayer::new(&displayer).print(&encounter, &members);
+
+        assert!(document.starts_with("=== Bandit Gang — Reikwald Bandits ===\n\nRoster (2):\n  Leader: Otto Krieger, Outlaw 1 (Brigand), W 14\n  Hans Brenner, Outlaw 1 (Brigand), W 12\n\n--- 1/2 ---\nOtto Krieger, "));
+        assert!(document.contains("\n\n--- 2/2 ---\nHans Brenner, "));
+    }
+}
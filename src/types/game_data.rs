@@ -0,0 +1,764 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+
+use crate::interfaces::resource_bag::ResourceBag;
+use crate::models::{armour::Armour, base_attribute::BaseAttribute, creature::Creature, creature_trait::CreatureTrait, details::DetailTables, encounter::Encounter, generation_base::GenerationBase, name_rules::NameRules, personality::PersonalityTables, profession::Profession, size::Size, profession_class::ProfessionClass, roll_table::{RollTable, RollTableEntry},
+                    skill_definition::SkillDefinition, specialisation, species::Species, spell::MagicGrant, status::Status, talent::Talent, talent_definition::TalentDefinition,
+                    trapping::Trapping, weapon::Weapon};
+
+const SKILLS: &str = include_str!("../../data/skills.json");
+const TALENTS: &str = include_str!("../../data/talents.json");
+const RANDOM_TALENTS: &str = include_str!("../../data/random_talents.json");
+const SPECIES: &str = include_str!("../../data/species.json");
+const CLASSES: &str = include_str!("../../data/classes.json");
+const CAREERS: &str = include_str!("../../data/careers.json");
+const NAMES: &str = include_str!("../../data/names.json");
+const DETAILS: &str = include_str!("../../data/details.json");
+const PERSONALITY: &str = include_str!("../../data/personality.json");
+const WEAPONS: &str = include_str!("../../data/weapons.json");
+const ARMOUR: &str = include_str!("../../data/armour.json");
+const TRAPPINGS: &str = include_str!("../../data/trappings.json");
+const MAGIC: &str = include_str!("../../data/magic.json");
+const CREATURES: &str = include_str!("../../data/creatures.json");
+const CREATURE_TRAITS: &str = include_str!("../../data/creature_traits.json");
+const REGIONS: &str = include_str!("../../data/regions.json");
+const ENCOUNTERS: &str = include_str!("../../data/encounters.json");
+
+const PROFESSION_LEVEL_SEPARATOR: char = '_';
+const SIZE_TRAIT: &str = "Size";
+const VARIANT_BASE: &str = "base";
+
+#[derive(Deserialize)]
+struct SkillRecord {
+    name: String,
+    characteristic: BaseAttribute,
+    #[serde(default)]
+    specialisations: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TalentRecord {
+    name: String,
+    max_level: u32,
+    description: String,
+    #[serde(default)]
+    specialisations: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TalentChoiceRecord {
+    Single(String),
+    Alternatives(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TrappingRecord {
+    Name(String),
+    Counted { name: String, count: u32 },
+}
+
+// Rules data of gear that is neither a weapon nor armour
+#[derive(Deserialize)]
+struct ItemRecord {
+    name: String,
+    encumbrance: u32,
+    #[serde(default)]
+    carries: u32,
+    #[serde(default)]
+    worn: bool,
+}
+
+#[derive(Deserialize)]
+struct CharacteristicsRecord {
+    weapon_skill: GenerationBase,
+    ballistic_skill: GenerationBase,
+    strength: GenerationBase,
+    toughness: GenerationBase,
+    initiative: GenerationBase,
+    agility: GenerationBase,
+    dexterity: GenerationBase,
+    intelligence: GenerationBase,
+    willpower: GenerationBase,
+    fellowship: GenerationBase,
+}
+
+#[derive(Deserialize)]
+struct SpeciesRecord {
+    id: String,
+    #[serde(default)]
+    base: Option<String>,
+    name: String,
+    characteristics: CharacteristicsRecord,
+    movement: u32,
+    #[serde(default)]
+    size: Size,
+    fate: u32,
+    resilience: u32,
+    extra_points: u32,
+    skills: Vec<String>,
+    talents: Vec<TalentChoiceRecord>,
+    random_talents: u32,
+    trappings: Vec<TrappingRecord>,
+}
+
+#[derive(Deserialize)]
+struct ClassRecord {
+    id: String,
+    name: String,
+    trappings: Vec<TrappingRecord>,
+}
+
+#[derive(Deserialize)]
+struct CareerLevelRecord {
+    title: String,
+    status: Status,
+    characteristics: Vec<BaseAttribute>,
+    skills: Vec<String>,
+    talents: Vec<String>,
+    trappings: Vec<TrappingRecord>,
+}
+
+#[derive(Deserialize)]
+struct CareerRecord {
+    id: String,
+    name: String,
+    class: String,
+    // chance of the career in each species' d100 career table
+    #[serde(default)]
+    roll: HashMap<String, u32>,
+    levels: Vec<CareerLevelRecord>,
+}
+
+#[derive(Deserialize)]
+struct NamesRecord {
+    species: String,
+    #[serde(flatten)]
+    rules: NameRules,
+}
+
+#[derive(Deserialize)]
+struct SpeciesDetailsRecord {
+    species: String,
+    #[serde(flatten)]
+    tables: DetailTables,
+}
+
+// Distinguishing marks are shared by all species
+#[derive(Deserialize)]
+struct DetailsRecord {
+    marks: RollTable,
+    species: Vec<SpeciesDetailsRecord>,
+}
+
+#[derive(Deserialize)]
+struct ClassPersonalityRecord {
+    class: String,
+    #[serde(flatten)]
+    tables: PersonalityTables,
+}
+
+// Personality traits are shared by all classes
+#[derive(Deserialize)]
+struct PersonalityRecord {
+    traits: Vec<String>,
+    classes: Vec<ClassPersonalityRecord>,
+}
+
+// Lores a spellcasting talent opens up, keyed by the talent's base name
+#[derive(Deserialize)]
+struct MagicRecord {
+    talent: String,
+    #[serde(flatten)]
+    grant: MagicGrant,
+}
+
+#[derive(Deserialize)]
+struct CreatureTraitDefinitionRecord {
+    name: String,
+    description: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CreatureTraitRecord {
+    Name(String),
+    Full(CreatureTrait),
+}
+
+// Demographics of a region, as a d100 table of species ids
+#[derive(Deserialize)]
+struct RegionRecord {
+    id: String,
+    species: RollTable,
+}
+
+#[derive(Deserialize)]
+struct EncounterRecord {
+    id: String,
+    #[serde(flatten)]
+    encounter: Encounter,
+}
+
+#[derive(Deserialize)]
+struct CreatureRecord {
+    id: String,
+    name: String,
+    characteristics: CharacteristicsRecord,
+    movement: u32,
+    traits: Vec<CreatureTraitRecord>,
+    optional_traits: Vec<CreatureTraitRecord>,
+    random_traits: u32,
+    trappings: Vec<TrappingRecord>,
+}
+
+// Rules data shipped with the application, resolved into models on request
+pub struct GameData {
+    skills: Vec<SkillRecord>,
+    talents: Vec<TalentRecord>,
+    random_talents: Vec<String>,
+    species: Vec<SpeciesRecord>,
+    classes: Vec<ClassRecord>,
+    careers: Vec<CareerRecord>,
+    names: Vec<NamesRecord>,
+    details: DetailsRecord,
+    personality: PersonalityRecord,
+    weapons: Vec<Weapon>,
+    armour: Vec<Armour>,
+    items: Vec<ItemRecord>,
+    magic: Vec<MagicRecord>,
+    creatures: Vec<CreatureRecord>,
+    creature_traits: Vec<CreatureTraitDefinitionRecord>,
+    regions: Vec<RegionRecord>,
+    encounters: Vec<EncounterRecord>,
+}
+
+impl GameData {
+    pub fn embedded() -> Self {
+        GameData {
+            skills: serde_json::from_str(SKILLS).expect("embedded skills data is invalid"),
+            talents: serde_json::from_str(TALENTS).expect("embedded talents data is invalid"),
+            random_talents: serde_json::from_str(RANDOM_TALENTS).expect("embedded random talents data is invalid"),
+            species: Self::parse_with_variants(SPECIES, "id").expect("embedded species data is invalid"),
+            classes: serde_json::from_str(CLASSES).expect("embedded classes data is invalid"),
+            careers: serde_json::from_str(CAREERS).expect("embedded careers data is invalid"),
+            names: Self::parse_with_variants(NAMES, "species").expect("embedded names data is invalid"),
+            details: Self::parse_details(DETAILS).expect("embedded details data is invalid"),
+            personality: serde_json::from_str(PERSONALITY).expect("embedded personality data is invalid"),
+            weapons: serde_json::from_str(WEAPONS).expect("embedded weapons data is invalid"),
+            armour: serde_json::from_str(ARMOUR).expect("embedded armour data is invalid"),
+            items: serde_json::from_str(TRAPPINGS).expect("embedded trappings data is invalid"),
+            magic: serde_json::from_str(MAGIC).expect("embedded magic data is invalid"),
+            creatures: serde_json::from_str(CREATURES).expect("embedded creatures data is invalid"),
+            creature_traits: serde_json::from_str(CREATURE_TRAITS).expect("embedded creature traits data is invalid"),
+            regions: serde_json::from_str(REGIONS).expect("embedded regions data is invalid"),
+            encounters: serde_json::from_str(ENCOUNTERS).expect("embedded encounters data is invalid"),
+        }
+    }
+
+    // Records with a "base" are patches over the record with that key, objects merge field by field and anything else is replaced
+    fn resolve_variants(records: Vec<Value>, key: &str) -> Result<Vec<Value>, String> {
+        let mut resolved: Vec<Value> = vec![];
+        for record in records {
+            let record = match record.get(VARIANT_BASE).and_then(Value::as_str) {
+                Some(base) => {
+                    let base = resolved.iter()
+                                       .find(|resolved| resolved.get(key).and_then(Value::as_str) == Some(base))
+                                       .ok_or(format!("unknown base \"{}\"", base))?;
+                    Self::patch(base.clone(), record)
+                }
+                None => record,
+            };
+            resolved.push(record);
+        }
+        Ok(resolved)
+    }
+
+    fn patch(base: Value, patch: Value) -> Value {
+        match (base, patch) {
+            (Value::Object(mut base), Value::Object(patch)) => {
+                for (field, value) in patch {
+                    let patched = match base.remove(&field) {
+                        Some(original) => Self::patch(original, value),
+                        None => value,
+                    };
+                    base.insert(field, patched);
+                }
+                Value::Object(base)
+            }
+            (_, patch) => patch,
+        }
+    }
+
+    fn parse_with_variants<T: DeserializeOwned>(json: &str, key: &str) -> Result<Vec<T>, String> {
+        let records: Vec<Value> = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        serde_json::from_value(Value::Array(Self::resolve_variants(records, key)?)).map_err(|error| error.to_string())
+    }
+
+    fn parse_details(json: &str) -> Result<DetailsRecord, String> {
+        let mut details: Value = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        let species: Vec<Value> = serde_json::from_value(details["species"].take()).map_err(|error| error.to_string())?;
+        details["species"] = Value::Array(Self::resolve_variants(species, "species")?);
+        serde_json::from_value(details).map_err(|error| error.to_string())
+    }
+
+    pub fn species_ids(&self) -> Vec<&str> {
+        self.species.iter().map(|species| species.id.as_str()).collect()
+    }
+
+    pub fn career_ids(&self) -> Vec<&str> {
+        self.careers.iter().map(|career| career.id.as_str()).collect()
+    }
+
+    pub fn class_ids(&self) -> Vec<&str> {
+        self.classes.iter().map(|class| class.id.as_str()).collect()
+    }
+
+    // Career table of a species, optionally restricted to a class. Variants roll on their base species' chances
+    pub fn career_table(&self, species: &str, class: Option<&str>) -> RollTable {
+        let base = self.species.iter().find(|record| record.id == species).and_then(|record| record.base.as_deref());
+        let mut up_to = 0;
+        let entries = self.careers
+                          .iter()
+                          .filter(|career| class.is_none_or(|class| career.class == class))
+                          .filter_map(|career| {
+                              let chance = career.roll.get(species).or_else(|| career.roll.get(base?))?;
+                              up_to += chance;
+                              Some(RollTableEntry { up_to, value: career.id.clone() })
+                          })
+                          .collect();
+        RollTable { entries }
+    }
+
+    pub fn region_ids(&self) -> Vec<&str> {
+        self.regions.iter().map(|region| region.id.as_str()).collect()
+    }
+
+    pub fn species_table(&self, region: &str) -> Option<&RollTable> {
+        self.regions.iter().find(|record| record.id == region).map(|record| &record.species)
+    }
+
+    pub fn encounter_ids(&self) -> Vec<&str> {
+        self.encounters.iter().map(|encounter| encounter.id.as_str()).collect()
+    }
+
+    pub fn creature_ids(&self) -> Vec<&str> {
+        self.creatures.iter().map(|creature| creature.id.as_str()).collect()
+    }
+
+    pub fn skill_names(&self) -> Vec<&str> {
+        self.skills.iter().map(|skill| skill.name.as_str()).collect()
+    }
+
+    pub fn talent_names(&self) -> Vec<&str> {
+        self.talents.iter().map(|talent| talent.name.as_str()).collect()
+    }
+
+    // "Melee (Basic)" is defined by its "Melee" entry
+    fn skill_definition(&self, name: &str) -> Option<SkillDefinition> {
+        self.skills
+            .iter()
+            .find(|skill| skill.name == specialisation::base_name(name))
+            .map(|skill| SkillDefinition { name: name.to_string(), base_attribute: skill.characteristic, specialisations: skill.specialisations.clone() })
+    }
+
+    fn talent_definition(&self, name: &str) -> Option<TalentDefinition> {
+        self.talents
+            .iter()
+            .find(|talent| talent.name == specialisation::base_name(name))
+            .map(|talent| TalentDefinition {
+                name: name.to_string(),
+                level: 1,
+                max_level: talent.max_level,
+                description: talent.description.clone(),
+                magic: self.magic.iter().find(|magic| magic.talent == talent.name).map(|magic| magic.grant.clone()),
+                specialisations: talent.specialisations.clone(),
+            })
+    }
+
+    fn talent(&self, name: &str) -> Option<Talent> {
+        self.talent_definition(name).as_ref().map(Talent::from)
+    }
+
+    fn creature_trait(&self, record: &CreatureTraitRecord) -> Option<CreatureTrait> {
+        let creature_trait = match record {
+            CreatureTraitRecord::Name(name) => CreatureTrait::new(name),
+            CreatureTraitRecord::Full(creature_trait) => creature_trait.clone(),
+        };
+        self.creature_traits
+            .iter()
+            .find(|definition| definition.name == creature_trait.name)
+            .map(|definition| CreatureTrait { description: definition.description.clone(), ..creature_trait })
+    }
+
+    // Size of a creature is written as its "Size (Large)" trait
+    fn creature_size(traits: &[CreatureTrait]) -> Option<Size> {
+        match traits.iter().find(|creature_trait| creature_trait.name == SIZE_TRAIT) {
+            Some(size) => size.specialisation.as_deref()?.parse().ok(),
+            None => Some(Size::default()),
+        }
+    }
+
+    fn trapping(&self, record: &TrappingRecord) -> Trapping {
+        let trapping = match record {
+            TrappingRecord::Name(name) => Trapping::new(name, 1),
+            TrappingRecord::Counted { name, count } => Trapping::new(name, *count),
+        };
+        let weapon = self.weapons.iter().find(|weapon| weapon.name == trapping.name).cloned();
+        let armour = self.armour.iter().find(|armour| armour.name == trapping.name).cloned();
+        let item = self.items.iter().find(|item| item.name == trapping.name);
+        Trapping {
+            encumbrance: item.map(|item| item.encumbrance)
+                             .or(weapon.as_ref().map(|weapon| weapon.encumbrance))
+                             .or(armour.as_ref().map(|armour| armour.encumbrance))
+                             .unwrap_or_default(),
+            carries: item.map_or(0, |item| item.carries),
+            worn: item.is_some_and(|item| item.worn) || armour.is_some(),
+            weapon,
+            armour,
+            ..trapping
+        }
+    }
+
+    fn profession_class(&self, id: &str) -> Option<ProfessionClass> {
+        self.classes.iter().find(|class| class.id == id).map(|class| ProfessionClass {
+            trappings: class.trappings.iter().map(|trapping| self.trapping(trapping)).collect(),
+            personality: self.personality
+                             .classes
+                             .iter()
+                             .find(|personality| personality.class == class.name)
+                             .map(|personality| PersonalityTables { traits: self.personality.traits.clone(), ..personality.tables.clone() }),
+        })
+    }
+}
+
+impl ResourceBag<Species> for GameData {
+    fn get_by_id(&self, id: &str) -> Option<Species> {
+        let record = self.species.iter().find(|species| species.id == id)?;
+        let characteristics = &record.characteristics;
+
+        Some(Species {
+            name: record.name.clone(),
+            weapon_skill: characteristics.weapon_skill.clone(),
+            ballistic_skill: characteristics.ballistic_skill.clone(),
+            strength: characteristics.strength.clone(),
+            toughness: characteristics.toughness.clone(),
+            initiative: characteristics.initiative.clone(),
+            agility: characteristics.agility.clone(),
+            dexterity: characteristics.dexterity.clone(),
+            intelligence: characteristics.intelligence.clone(),
+            willpower: characteristics.willpower.clone(),
+            fellowship: characteristics.fellowship.clone(),
+            movement: record.movement,
+            size: record.size,
+            fate: record.fate,
+            resilience: record.resilience,
+            extra_points: record.extra_points,
+            trappings: record.trappings.iter().map(|trapping| self.trapping(trapping)).collect(),
+            talents: record.talents
+                           .iter()
+                           .map(|choice| match choice {
+                               TalentChoiceRecord::Single(name) => self.talent(name).map(|talent| vec![talent]),
+                               TalentChoiceRecord::Alternatives(names) => names.iter().map(|name| self.talent(name)).collect(),
+                           })
+                           .collect::<Option<Vec<Vec<Talent>>>>()?,
+            possible_talents: self.random_talents.iter().map(|name| self.talent(name)).collect::<Option<Vec<Talent>>>()?,
+            random_talents: record.random_talents,
+            possible_skills: record.skills.iter().map(|name| self.skill_definition(name)).collect::<Option<Vec<SkillDefinition>>>()?,
+            names: self.names.iter().find(|names| names.species == id).map(|names| names.rules.clone()).unwrap_or_default(),
+            details: self.details.species
+                                 .iter()
+                                 .find(|details| details.species == id)
+                                 .map(|details| DetailTables { marks: self.details.marks.clone(), ..details.tables.clone() }),
+        })
+    }
+}
+
+// Skills and talents are looked up by name, specialised ones like "Melee (Basic)" by their base entry
+impl ResourceBag<SkillDefinition> for GameData {
+    fn get_by_id(&self, id: &str) -> Option<SkillDefinition> {
+        self.skill_definition(id)
+    }
+}
+
+impl ResourceBag<Talent> for GameData {
+    fn get_by_id(&self, id: &str) -> Option<Talent> {
+        self.talent(id)
+    }
+}
+
+impl ResourceBag<Encounter> for GameData {
+    fn get_by_id(&self, id: &str) -> Option<Encounter> {
+        self.encounters.iter().find(|encounter| encounter.id == id).map(|record| record.encounter.clone())
+    }
+}
+
+impl ResourceBag<Creature> for GameData {
+    fn get_by_id(&self, id: &str) -> Option<Creature> {
+        let record = self.creatures.iter().find(|creature| creature.id == id)?;
+        let characteristics = &record.characteristics;
+        let traits = record.traits.iter().map(|creature_trait| self.creature_trait(creature_trait)).collect::<Option<Vec<CreatureTrait>>>()?;
+
+        Some(Creature {
+            name: record.name.clone(),
+            weapon_skill: characteristics.weapon_skill.clone(),
+            ballistic_skill: characteristics.ballistic_skill.clone(),
+            strength: characteristics.strength.clone(),
+            toughness: characteristics.toughness.clone(),
+            initiative: characteristics.initiative.clone(),
+            agility: characteristics.agility.clone(),
+            dexterity: characteristics.dexterity.clone(),
+            intelligence: characteristics.intelligence.clone(),
+            willpower: characteristics.willpower.clone(),
+            fellowship: characteristics.fellowship.clone(),
+            movement: record.movement,
+            size: Self::creature_size(&traits)?,
+            traits,
+            optional_traits: record.optional_traits.iter().map(|creature_trait| self.creature_trait(creature_trait)).collect::<Option<Vec<CreatureTrait>>>()?,
+            random_traits: record.random_traits,
+            trappings: record.trappings.iter().map(|trapping| self.trapping(trapping)).collect(),
+        })
+    }
+}
+
+// Profession ids are career ids suffixed with the level, e.g. "soldier_2"
+impl ResourceBag<Profession> for GameData {
+    fn get_by_id(&self, id: &str) -> Option<Profession> {
+        let (career_id, level) = id.rsplit_once(PROFESSION_LEVEL_SEPARATOR)?;
+        let level: u8 = level.parse().ok()?;
+        let career = self.careers.iter().find(|career| career.id == career_id)?;
+        let record = career.levels.get(usize::from(level).checked_sub(1)?)?;
+
+        Some(Profession {
+            trappings: record.trappings.iter().map(|trapping| self.trapping(trapping)).collect(),
+            skills: record.skills.iter().map(|name| self.skill_definition(name)).collect::<Option<Vec<SkillDefinition>>>()?,
+            talents: record.talents.iter().map(|name| self.talent_definition(name)).collect::<Option<Vec<TalentDefinition>>>()?,
+            characteristics: record.characteristics.clone(),
+            profession_level: level,
+            name: career.name.clone(),
+            title: record.title.clone(),
+            status: record.status,
+            class: self.profession_class(&career.class)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_species_resolve() {
+        let data = GameData::embedded();
+        for id in data.species_ids() {
+            assert!(ResourceBag::<Species>::get_by_id(&data, id).is_some(), "species {} does not resolve", id);
+        }
+    }
+
+    #[test]
+    fn variants_patch_their_base_species() {
+        let data = GameData::embedded();
+        let human: Species = data.get_by_id("human").unwrap();
+        let norscan: Species = data.get_by_id("norscan").unwrap();
+        assert_eq!(norscan.name, "Norscan");
+        assert_eq!(norscan.toughness, "25+2d10".parse().unwrap());
+        assert_eq!(norscan.strength, human.strength);
+        assert_eq!((norscan.movement, norscan.fate, norscan.random_talents), (human.movement, human.fate, human.random_talents));
+        assert!(norscan.possible_skills.iter().any(|skill| skill.name == "Language (Norse)"));
+        assert_ne!(norscan.names.forenames.male, human.names.forenames.male);
+        assert_eq!(norscan.details.as_ref().map(|details| details.age.clone()), human.details.as_ref().map(|details| details.age.clone()));
+
+        let tilean: Species = data.get_by_id("tilean").unwrap();
+        assert_eq!(tilean.talents.len(), human.talents.len());
+        assert_eq!(tilean.names.career_surnames.get("Soldier").map(String::as_str), Some("Soldato"));
+    }
+
+    #[test]
+    fn patch_merges_objects_and_replaces_the_rest() {
+        let base = serde_json::json!({"id": "human", "characteristics": {"strength": "20", "toughness": "20"}, "skills": ["Charm", "Cool"]});
+        let patch = serde_json::json!({"id": "norscan", "base": "human", "characteristics": {"toughness": "25"}, "skills": ["Row"]});
+        let resolved = GameData::resolve_variants(vec![base, patch], "id").unwrap();
+        assert_eq!(resolved[1], serde_json::json!({"id": "norscan", "base": "human", "characteristics": {"strength": "20", "toughness": "25"}, "skills": ["Row"]}));
+        assert!(GameData::resolve_variants(vec![serde_json::json!({"id": "tilean", "base": "elf"})], "id").is_err());
+    }
+
+    #[test]
+    fn all_species_have_names_and_details() {
+        let data = GameData::embedded();
+        for id in data.species_ids() {
+            let species: Species = data.get_by_id(id).unwrap();
+            assert!(!species.names.forenames.male.is_empty() && !species.names.surname_forms.is_empty(), "species {} has no names", id);
+            assert!(species.details.is_some_and(|details| !details.marks.entries.is_empty()), "species {} has no details", id);
+        }
+    }
+
+    #[test]
+    fn all_creatures_resolve() {
+        let data = GameData::embedded();
+        for id in data.creature_ids() {
+            assert!(ResourceBag::<Creature>::get_by_id(&data, id).is_some(), "creature {} does not resolve", id);
+        }
+    }
+
+    #[test]
+    fn encounter_members_resolve() {
+        let data = GameData::embedded();
+        for id in data.encounter_ids() {
+            let encounter: Encounter = data.get_by_id(id).unwrap();
+            for member in &encounter.members {
+                let species = member.species.as_ref().unwrap_or(&encounter.species);
+                assert!(species == "random" || data.species_ids().contains(&species.as_str()), "encounter {} has unknown species {}", id, species);
+                for career in member.careers.split(' ').filter(|career| !career.starts_with("random")) {
+                    assert!(ResourceBag::<Profession>::get_by_id(&data, career).is_some(), "encounter {} has unknown career {}", id, career);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn size_of_species_and_creatures() {
+        let data = GameData::embedded();
+        assert_eq!(ResourceBag::<Species>::get_by_id(&data, "halfling").unwrap().size, Size::Small);
+        assert_eq!(ResourceBag::<Species>::get_by_id(&data, "human").unwrap().size, Size::Average);
+        assert_eq!(ResourceBag::<Creature>::get_by_id(&data, "bear").unwrap().size, Size::Large);
+        assert_eq!(ResourceBag::<Creature>::get_by_id(&data, "wolf").unwrap().size, Size::Average);
+    }
+
+    #[test]
+    fn creature_traits_carry_descriptions() {
+        let wolf: Creature = GameData::embedded().get_by_id("wolf").unwrap();
+        let bite = wolf.traits.iter().find(|creature_trait| creature_trait.name == "Bite").unwrap();
+        assert_eq!(bite.damage.map(|damage| damage.to_string()), Some("+SB+3".to_string()));
+        assert!(!bite.description.is_empty());
+    }
+
+    #[test]
+    fn all_career_levels_resolve() {
+        let data = GameData::embedded();
+        for career in data.careers.iter() {
+            for level in 1..=career.levels.len() {
+                let id = format!("{}_{}", career.id, level);
+                assert!(ResourceBag::<Profession>::get_by_id(&data, &id).is_some(), "profession {} does not resolve", id);
+            }
+        }
+    }
+
+    #[test]
+    fn profession_by_id() {
+        let profession: Profession = GameData::embedded().get_by_id("witch_hunter_2").unwrap();
+        assert_eq!(profession.name, "Witch Hunter");
+        assert_eq!(profession.title, "Witch Hunter");
+        assert_eq!(profession.profession_level, 2);
+        assert_eq!(profession.status.to_string(), "Silver 3");
+        assert!(profession.class.trappings.iter().any(|trapping| trapping.name == "Fine Clothing"));
+    }
+
+    #[test]
+    fn unknown_profession_level() {
+        let data = GameData::embedded();
+        assert!(ResourceBag::<Profession>::get_by_id(&data, "soldier_0").is_none());
+        assert!(ResourceBag::<Profession>::get_by_id(&data, "soldier_5").is_none());
+        assert!(ResourceBag::<Profession>::get_by_id(&data, "soldier").is_none());
+    }
+
+    #[test]
+    fn career_tables_cover_a_d100() {
+        let data = GameData::embedded();
+        for id in data.species_ids() {
+            assert_eq!(data.career_table(id, None).dice(), "d100".parse().unwrap(), "career table of {} is not a d100", id);
+        }
+        assert_eq!(data.career_table("norscan", None).lookup(1), Some("scholar"));
+        assert_eq!(data.career_table("ogre", Some("academics")).entries.len(), 0);
+        let rogues = data.career_table("human", Some("rogues"));
+        assert_eq!((rogues.lookup(1), rogues.lookup(16)), (Some("outlaw"), Some("thief")));
+        assert_eq!(rogues.dice(), "d16".parse().unwrap());
+    }
+
+    #[test]
+    fn regions_roll_known_species() {
+        let data = GameData::embedded();
+        for id in data.region_ids() {
+            let table = data.species_table(id).unwrap();
+            assert_eq!(table.dice(), "d100".parse().unwrap(), "species table of {} is not a d100", id);
+            for entry in &table.entries {
+                assert!(data.species_ids().contains(&entry.value.as_str()), "region {} rolls unknown species {}", id, entry.value);
+            }
+        }
+        let reikland = data.species_table("reikland").unwrap();
+        assert_eq!((reikland.lookup(90), reikland.lookup(91)), (Some("human"), Some("halfling")));
+        assert!(data.species_table("lustria").is_none());
+    }
+
+    #[test]
+    fn weapons_and_armour_attach_to_trappings() {
+        let profession: Profession = GameData::embedded().get_by_id("soldier_2").unwrap();
+        let helmet = profession.trappings.iter().find(|trapping| trapping.name == "Helmet").unwrap();
+        assert_eq!(helmet.armour.as_ref().map(|armour| armour.armour_points), Some(2));
+        assert!(helmet.weapon.is_none());
+        let warriors_hand_weapon = profession.class.trappings.iter().find(|trapping| trapping.name == "Hand Weapon").unwrap();
+        assert_eq!(warriors_hand_weapon.weapon.as_ref().map(|weapon| weapon.damage.to_string()), Some("+SB+4".to_string()));
+    }
+
+    #[test]
+    fn trappings_carry_encumbrance() {
+        let profession: Profession = GameData::embedded().get_by_id("villager_1").unwrap();
+        let encumbrance = |name: &str| profession.class.trappings.iter().find(|trapping| trapping.name == name).map(|trapping| (trapping.encumbrance, trapping.carries, trapping.worn));
+        assert_eq!(encumbrance("Cloak"), Some((1, 0, true)));
+        assert_eq!(encumbrance("Sling Bag"), Some((1, 2, false)));
+        assert_eq!(encumbrance("Dagger"), Some((0, 0, false)));
+    }
+
+    #[test]
+    fn spellcasting_talents_carry_their_lores() {
+        let profession: Profession = GameData::embedded().get_by_id("witch_2").unwrap();
+        let arcane_magic = profession.talents.iter().find(|talent| talent.name == "Arcane Magic (Witchery)").unwrap();
+        assert!(arcane_magic.magic.as_ref().is_some_and(|grant| grant.lore("Witchery").is_some()));
+        assert!(profession.talents.iter().filter(|talent| talent.name != arcane_magic.name).all(|talent| talent.magic.is_none()));
+    }
+
+    #[test]
+    fn specialised_lores_exist() {
+        let data = GameData::embedded();
+        for career in data.careers.iter() {
+            for (level, _) in career.levels.iter().enumerate() {
+                let profession: Profession = data.get_by_id(&format!("{}_{}", career.id, level + 1)).unwrap();
+                for talent in profession.talents.iter().map(Talent::from).filter(|talent| talent.magic.is_some() && !talent.is_any_specialisation()) {
+                    let grant = talent.magic.as_ref().unwrap();
+                    assert!(talent.specialisation().map_or(grant.lores.len() == 1, |lore| grant.lore(lore).is_some()), "talent {} has no lore", talent.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn all_classes_have_personality_tables() {
+        let data = GameData::embedded();
+        for class in data.classes.iter() {
+            let personality = data.profession_class(&class.id).unwrap().personality;
+            assert!(personality.is_some_and(|tables| !tables.traits.is_empty() && !tables.secrets.is_empty()), "class {} has no personality", class.id);
+        }
+    }
+
+    #[test]
+    fn species_skill_specialisation_keeps_base_attribute() {
+        let species: Species = GameData::embedded().get_by_id("human").unwrap();
+        let melee = species.possible_skills.iter().find(|skill| skill.name == "Melee (Basic)").unwrap();
+        assert_eq!(melee.base_attribute, BaseAttribute::WeaponSkill);
+        assert_eq!(species.fate, 2);
+        assert_eq!(species.extra_points, 3);
+    }
+
+    #[test]
+    fn skills_and_talents_by_name() {
+        let data = GameData::embedded();
+        let skill: SkillDefinition = data.get_by_id("Melee (Polearm)").unwrap();
+        assert_eq!((skill.name.as_str(), skill.base_attribute), ("Melee (Polearm)", BaseAttribute::WeaponSkill));
+        let talent: Talent = data.get_by_id("Very Strong").unwrap();
+        assert_eq!(talent.level, 1);
+        assert!(ResourceBag::<Talent>::get_by_id(&data, "Very Clever Indeed").is_none());
+        assert!(data.skill_names().contains(&"Melee") && data.talent_names().contains(&"Very Strong"));
+    }
+}
@@ -1,36 +1,59 @@
+use std::collections::HashMap;
+
 use crate::models::consts::{MIN_PROFESSION_LEVEL, MAX_PROFESSION_LEVEL};
 use super::command::CommandError;
+use super::command_option::CommandOptions;
 
+pub const RANDOM_CAREER: &str = "random";
+// species rolled on the demographics of the region
+pub const RANDOM_SPECIES: &str = "random";
 
 pub struct GenerateNpcCommandArgs<'a>(pub &'a Vec<String>);
 
 impl<'a> std::ops::Deref for GenerateNpcCommandArgs<'a> {
     type Target = Vec<String>;
     fn deref(&self) -> &Vec<String> {
-        &self.0
+        self.0
     }
 }
 
 impl<'a> GenerateNpcCommandArgs<'a> {
-    pub fn professions(&self, arg_offset: Option<usize>)->Option<std::str::Split<char>> {
-        let arg_offset = if let Some(arg_offset) = arg_offset { arg_offset } else { 0 };
+    pub fn professions(&self, arg_offset: Option<usize>)->Option<std::str::Split<'a, char>> {
+        let arg_offset = arg_offset.unwrap_or_default();
 
-        if let Some(professions) = self.0.iter().skip(arg_offset).next() {
-            return Some(professions.split(' '))
-        }
-        else  {
-            return None
-        }
+        CommandOptions(self.0).positional()
+                              .get(arg_offset)
+                              .map(|professions| professions.split(' '))
     }
 
-    pub fn species(&self, arg_offset: Option<usize>)->Option<&String> {
-        let arg_offset = if let Some(arg_offset) = arg_offset { arg_offset } else { 0 };
+    pub fn species(&self, arg_offset: Option<usize>)->Option<&'a String> {
+        let arg_offset = arg_offset.unwrap_or_default();
 
-        if let Some(species) = self.0.iter().skip(arg_offset + 1).next() {
-            return Some(species)
-        }
-        else  {
-            return None
+        CommandOptions(self.0).positional()
+                              .get(arg_offset + 1)
+                              .copied()
+    }
+
+    // Careers are taken in order and each stands for all of its levels up to the given one, e.g. "soldier_2" is ["soldier_1", "soldier_2"].
+    // Returning to a career only adds the levels not completed before
+    pub fn profession_ids<'b>(professions: impl Iterator<Item=&'b str>)->Vec<String> {
+        let mut completed: HashMap<&str, u32> = HashMap::new();
+        professions.flat_map(|profession| {
+                       let (career, level) = profession.rsplit_once('_').unwrap_or((profession, "1"));
+                       let level: u32 = level.parse().unwrap_or(MIN_PROFESSION_LEVEL);
+                       let first = completed.get(career).map_or(MIN_PROFESSION_LEVEL, |completed| completed + 1);
+                       completed.insert(career, level.max(first - 1));
+                       (first..=level).map(move |level| format!("{}_{}", career, level))
+                   })
+                   .collect()
+    }
+
+    // "random" and "random_2" stand for a career rolled on the species' career table, taken to the given level
+    pub fn random_career_level(profession: &str)->Option<&str> {
+        match profession.rsplit_once('_') {
+            Some((RANDOM_CAREER, level)) => Some(level),
+            None if profession == RANDOM_CAREER => Some("1"),
+            _ => None,
         }
     }
 
@@ -53,13 +76,14 @@ impl<'a> GenerateNpcCommandArgs<'a> {
 
     fn is_valid_species(species: &str)->bool {
         species.len() > 1 &&
-        species.chars().find(|c| !c.is_lowercase()).is_none()
+        species.chars().all(|c| c.is_lowercase() || c == '_')
     }
     fn is_valid_profession(profession: &str)->bool {
+        profession == RANDOM_CAREER ||
         profession.len() > 2 &&
         profession.chars().rev().skip(2).find(|c: &char| !c.is_lowercase() && *c != '_').is_none() &&
         profession.chars().rev().nth(1).unwrap() == '_' &&
-        profession.ends_with(|c: char| c.to_digit(10).is_some_and(|level| (MIN_PROFESSION_LEVEL..MAX_PROFESSION_LEVEL).contains(&level)))
+        profession.ends_with(|c: char| c.to_digit(10).is_some_and(|level| (MIN_PROFESSION_LEVEL..=MAX_PROFESSION_LEVEL).contains(&level)))
     }
 }
 
@@ -70,12 +94,13 @@ mod test {
     use super::*;
     #[test] 
     fn is_valid_profession() {
-        assert_eq!(GenerateNpcCommandArgs::is_valid_profession("a_b_3"),true);
+        assert!(GenerateNpcCommandArgs::is_valid_profession("a_b_3"));
+        assert!(GenerateNpcCommandArgs::is_valid_profession("a_b_4"));
     }
 
     #[test] 
     fn is_not_valid_profession() {
-        assert_eq!(GenerateNpcCommandArgs::is_valid_profession("a_b_4"),false);
+        assert!(!GenerateNpcCommandArgs::is_valid_profession("a_b_5"));
     }
 
     #[test] 
@@ -86,12 +111,36 @@ mod test {
         assert_eq!(professions,(vec!["aaaa", "bb_bb"]));
     }
 
+    #[test] 
+    fn profession_ids() {
+        let professions_string: Vec<String> = vec!["soldier_3 witch_hunter_1".into()];
+        let generate_npc_command_args = GenerateNpcCommandArgs(&professions_string);
+        assert_eq!(GenerateNpcCommandArgs::profession_ids(generate_npc_command_args.professions(None).unwrap()),
+                   vec!["soldier_1", "soldier_2", "soldier_3", "witch_hunter_1"]);
+    }
+
+    #[test] 
+    fn profession_ids_when_returning_to_a_career() {
+        let professions_string: Vec<String> = vec!["soldier_2 outlaw_1 soldier_3".into()];
+        let generate_npc_command_args = GenerateNpcCommandArgs(&professions_string);
+        assert_eq!(GenerateNpcCommandArgs::profession_ids(generate_npc_command_args.professions(None).unwrap()),
+                   vec!["soldier_1", "soldier_2", "outlaw_1", "soldier_3"]);
+    }
+
+    #[test] 
+    fn random_career() {
+        assert!(GenerateNpcCommandArgs::is_valid_profession("random"));
+        assert_eq!(GenerateNpcCommandArgs::random_career_level("random"), Some("1"));
+        assert_eq!(GenerateNpcCommandArgs::random_career_level("random_3"), Some("3"));
+        assert_eq!(GenerateNpcCommandArgs::random_career_level("soldier_3"), None);
+    }
+
     #[test] 
     fn validate_professions() {
         let professions_string: Vec<String> = vec!["generate-npc".into(), "aaaa_1 bb_bb_2".into(), "aaaa".into()];
         let generate_npc_command_args = GenerateNpcCommandArgs(&professions_string);
         
-        assert_eq!(generate_npc_command_args.validate_professions(Some(1)).is_ok(), true);
+        assert!(generate_npc_command_args.validate_professions(Some(1)).is_ok());
     }
 
     #[test] 
@@ -107,6 +156,13 @@ mod test {
         let professions_string: Vec<String> = vec!["generate-npc".into(), "aaaa_1 bb_bb_2".into(), "aaaa".into()];
         let generate_npc_command_args = GenerateNpcCommandArgs(&professions_string);
         
-        assert_eq!(generate_npc_command_args.validate_species(Some(1)).is_ok(), true);
+        assert!(generate_npc_command_args.validate_species(Some(1)).is_ok());
+    }
+
+    #[test] 
+    fn is_valid_species() {
+        assert!(GenerateNpcCommandArgs::is_valid_species("wood_elf"));
+        assert!(GenerateNpcCommandArgs::is_valid_species(RANDOM_SPECIES));
+        assert!(!GenerateNpcCommandArgs::is_valid_species("Dwarf"));
     }
 }
@@ -1,6 +1,21 @@
 pub mod app;
+pub mod assessment_displayer;
+pub mod campaign;
 pub mod command;
+pub mod command_option;
 pub mod generate_npc_command_args;
 pub mod thread_based_random_provider;
+pub mod seeded_random_provider;
 pub mod console_character_displayer;
+pub mod console_settings;
+pub mod console_table;
 pub mod character_attributes_iter;
+pub mod compact_character_displayer;
+pub mod display_format;
+pub mod encounter_displayer;
+pub mod game_data;
+pub mod repl;
+pub mod random_character_generator;
+pub mod name_generator;
+pub mod npc_library;
+pub mod data_directory;
@@ -0,0 +1,119 @@
+use crate::interfaces::random_provider::RandomProvider;
+use crate::models::{gender::Gender, name_rules::NameRules};
+
+const SURNAME_TOKEN: &str = "{surname}";
+const PLACE_TOKEN: &str = "{place}";
+const CAREER_TOKEN: &str = "{career}";
+
+pub struct NameGenerator<'a, R: RandomProvider> {
+    random_provider: &'a R,
+}
+
+impl<'a, R: RandomProvider> NameGenerator<'a, R> {
+    pub fn new(random_provider: &'a R) -> Self {
+        NameGenerator { random_provider }
+    }
+
+    // Forename followed by a surname when the rules allow one, empty if the rules have no names at all
+    pub fn generate(&self, rules: &NameRules, gender: Gender, career: Option<&str>) -> String {
+        [self.forename(rules, gender), self.surname(rules, career)]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    // The syllable rule counts as one more entry of the name list
+    fn forename(&self, rules: &NameRules, gender: Gender) -> Option<String> {
+        let names = rules.forenames.get(gender);
+        let suffixes = rules.suffixes.get(gender);
+        let can_build = !rules.prefixes.is_empty() && !suffixes.is_empty();
+
+        let index = self.random_provider.pick_index(names.len() + usize::from(can_build))?;
+        match names.get(index) {
+            Some(name) => Some(name.clone()),
+            None => Some(format!("{}{}", self.pick(&rules.prefixes)?, self.pick(suffixes)?)),
+        }
+    }
+
+    fn surname(&self, rules: &NameRules, career: Option<&str>) -> Option<String> {
+        let form = self.pick(&rules.surname_forms)?;
+        let mut surname = form.to_string();
+        if surname.contains(CAREER_TOKEN) {
+            match career.and_then(|career| rules.career_surnames.get(career)) {
+                Some(career_surname) => surname = surname.replace(CAREER_TOKEN, career_surname),
+                None => surname = surname.replace(CAREER_TOKEN, SURNAME_TOKEN),
+            }
+        }
+        if surname.contains(SURNAME_TOKEN) {
+            surname = surname.replace(SURNAME_TOKEN, self.pick(&rules.surnames)?);
+        }
+        if surname.contains(PLACE_TOKEN) {
+            surname = surname.replace(PLACE_TOKEN, self.pick(&rules.places)?);
+        }
+        Some(surname)
+    }
+
+    fn pick<'b>(&self, values: &'b [String]) -> Option<&'b str> {
+        self.random_provider.pick_index(values.len()).map(|index| values[index].as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::generation_base::GenerationBase;
+    use crate::models::name_rules::GenderedNames;
+
+    // Always picks the last element
+    struct HighestRandomProvider {}
+
+    impl RandomProvider for HighestRandomProvider {
+        fn generate(&self, base: &GenerationBase) -> u32 {
+            base.base_value + base.dices.iter().sum::<u32>()
+        }
+
+        fn fork(&self, _stream: u64) -> Self {
+            HighestRandomProvider {}
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn rules() -> NameRules {
+        NameRules {
+            forenames: GenderedNames { female: strings(&["Greta"]), male: strings(&["Otto"]), neutral: vec![] },
+            prefixes: strings(&["Sieg"]),
+            suffixes: GenderedNames { female: strings(&["linde"]), male: strings(&["mund"]), neutral: vec![] },
+            surnames: strings(&["Schmidt"]),
+            places: strings(&["Übersreik"]),
+            career_surnames: [("Hunter".to_string(), "Jäger".to_string())].into_iter().collect(),
+            surname_forms: strings(&["{career}"]),
+        }
+    }
+
+    #[test]
+    fn name_built_from_syllables() {
+        assert_eq!(NameGenerator::new(&HighestRandomProvider{}).generate(&rules(), Gender::Male, Some("Hunter")), "Siegmund Jäger");
+    }
+
+    #[test]
+    fn career_surname_falls_back_to_surname() {
+        assert_eq!(NameGenerator::new(&HighestRandomProvider{}).generate(&rules(), Gender::Female, Some("Soldier")), "Sieglinde Schmidt");
+    }
+
+    #[test]
+    fn surname_from_place() {
+        let rules = NameRules { surname_forms: strings(&["von {place}"]), ..rules() };
+        assert_eq!(NameGenerator::new(&HighestRandomProvider{}).generate(&rules, Gender::Male, None), "Siegmund von Übersreik");
+    }
+
+    #[test]
+    fn missing_names_are_skipped() {
+        let generator = NameGenerator::new(&HighestRandomProvider{});
+        assert_eq!(generator.generate(&rules(), Gender::Neutral, None), "Schmidt");
+        assert_eq!(generator.generate(&NameRules::default(), Gender::Male, None), "");
+    }
+}
@@ -0,0 +1,134 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::interfaces::npc_store::NpcStore;
+use crate::models::saved_npc::SavedNpc;
+use super::command::CommandError;
+use super::data_directory::data_directory;
+
+const LIBRARY_DIRECTORY: &str = "npcs";
+const NPC_EXTENSION: &str = "json";
+
+// Saved NPCs, one JSON file per NPC named after its id
+pub struct NpcLibrary {
+    directory: PathBuf,
+}
+
+impl NpcLibrary {
+    pub fn new(directory: PathBuf) -> Self {
+        NpcLibrary { directory }
+    }
+
+    pub fn default_directory() -> Option<PathBuf> {
+        data_directory().map(|directory| directory.join(LIBRARY_DIRECTORY))
+    }
+
+    // Ids are file names, so no paths
+    pub fn is_valid_id(id: &str) -> bool {
+        !id.is_empty() && id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, CommandError> {
+        if !Self::is_valid_id(id) {
+            Err(CommandError::InvalidArguments)?
+        }
+        Ok(self.directory.join(id).with_extension(NPC_EXTENSION))
+    }
+}
+
+impl NpcStore for NpcLibrary {
+    // An NPC saved under an id that is taken replaces the one before
+    fn save(&self, npc: &SavedNpc) -> Result<PathBuf, CommandError> {
+        let path = self.path(&npc.id)?;
+        let json = serde_json::to_string_pretty(npc).map_err(|_| CommandError::IoFailure)?;
+        fs::create_dir_all(&self.directory).map_err(|_| CommandError::IoFailure)?;
+        fs::write(&path, json).map_err(|_| CommandError::IoFailure)?;
+        Ok(path)
+    }
+
+    fn load(&self, id: &str) -> Result<SavedNpc, CommandError> {
+        let json = fs::read_to_string(self.path(id)?).map_err(|error| match error.kind() {
+            ErrorKind::NotFound => CommandError::UnknownNpc,
+            _ => CommandError::IoFailure,
+        })?;
+        serde_json::from_str(&json).map_err(|_| CommandError::IoFailure)
+    }
+
+    // Files that are not saved NPCs are skipped
+    fn list(&self) -> Result<Vec<SavedNpc>, CommandError> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(_) => Err(CommandError::IoFailure)?,
+        };
+        let mut npcs: Vec<SavedNpc> = entries.filter_map(Result::ok)
+                                             .map(|entry| entry.path())
+                                             .filter(|path| path.extension().is_some_and(|extension| extension == NPC_EXTENSION))
+                                             .filter_map(|path| fs::read_to_string(path).ok())
+                                             .filter_map(|json| serde_json::from_str(&json).ok())
+                                             .collect();
+        npcs.sort_by(|first, second| first.id.cmp(&second.id));
+        Ok(npcs)
+    }
+
+    fn delete(&self, id: &str) -> Result<(), CommandError> {
+        fs::remove_file(self.path(id)?).map_err(|error| match error.kind() {
+            ErrorKind::NotFound => CommandError::UnknownNpc,
+            _ => CommandError::IoFailure,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::interfaces::{character_generator::CharacterGenerator, resource_bag::ResourceBag};
+    use crate::models::{profession::Profession, species::Species};
+    use crate::types::{game_data::GameData, random_character_generator::RandomCharacterGenerator, seeded_random_provider::SeededRandomProvider};
+
+    fn library(name: &str) -> NpcLibrary {
+        let directory = std::env::temp_dir().join(format!("wfrp-npc-library-{}-{}", name, std::process::id()));
+        fs::remove_dir_all(&directory).ok();
+        NpcLibrary::new(directory)
+    }
+
+    fn saved(id: &str, careers: &[&str], species: &str) -> SavedNpc {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = careers.iter().map(|id| data.get_by_id(id).unwrap()).collect();
+        let species_data: Species = data.get_by_id(species).unwrap();
+        let character = RandomCharacterGenerator::new(SeededRandomProvider::new(3)).with_details().generate(&professions, &species_data);
+        SavedNpc { id: id.into(), tags: vec!["altdorf".into()], seed: Some(3), careers: careers.iter().map(|career| career.to_string()).collect(),
+                   species: species.into(), options: vec!["--details".into()], character }
+    }
+
+    #[test]
+    fn saved_npcs_load_as_they_were() {
+        let library = library("load");
+        let npc = saved("ilsa", &["wizard_1", "wizard_2"], "high_elf");
+        library.save(&npc).unwrap();
+        let loaded = library.load("ilsa").unwrap();
+        assert_eq!((&loaded.id, &loaded.tags, loaded.seed, &loaded.options), (&npc.id, &npc.tags, npc.seed, &npc.options));
+        assert_eq!(serde_json::to_string(&loaded.character).unwrap(), serde_json::to_string(&npc.character).unwrap());
+        assert_eq!((loaded.character.species.as_str(), loaded.character.details), ("High Elf", npc.character.details));
+        assert_eq!(library.load("gunther").err(), Some(CommandError::UnknownNpc));
+        assert_eq!(library.load("../ilsa").err(), Some(CommandError::InvalidArguments));
+        fs::remove_dir_all(&library.directory).ok();
+    }
+
+    #[test]
+    fn list_and_delete() {
+        let library = library("list");
+        assert!(library.list().unwrap().is_empty());
+        library.save(&saved("gunther", &["soldier_1"], "human")).unwrap();
+        library.save(&saved("brokk", &["soldier_1"], "dwarf")).unwrap();
+        fs::write(library.directory.join("notes.txt"), "not an NPC").unwrap();
+        let ids: Vec<String> = library.list().unwrap().into_iter().map(|npc| npc.id).collect();
+        assert_eq!(ids, vec!["brokk", "gunther"]);
+
+        library.delete("brokk").unwrap();
+        assert_eq!(library.list().unwrap().len(), 1);
+        assert_eq!(library.delete("brokk").err(), Some(CommandError::UnknownNpc));
+        fs::remove_dir_all(&library.directory).ok();
+    }
+}
@@ -0,0 +1,828 @@
+use crate::interfaces::{character_generator::CharacterGenerator, creature_generator::CreatureGenerator, random_provider::RandomProvider};
+use super::name_generator::NameGenerator;
+use crate::models::{base_attribute::BaseAttribute, career_step::CareerStep, character::Character, character_section::CharacterSection, creature::Creature, creature_trait::CreatureTrait, details::{DetailTables, Details}, gender::Gender, generation_base::GenerationBase, personality::{Personality, PersonalityTables}, profession::Profession, roll_table::RollTable, skill::Skill, skill_definition::SkillDefinition, specialisation, species::Species,
+                    spell::{Lore, Spell}, stat_value::StatValue, talent::Talent, trapping::Trapping};
+
+// Three species skills start with 5 advances and three with 3
+const SPECIES_SKILL_ADVANCES: [u32; 6] = [5, 5, 5, 3, 3, 3];
+// Advances needed in every career characteristic and skill to complete a career level
+const CAREER_LEVEL_ADVANCES: u32 = 5;
+// Percent chance that a career is left with its level completed
+const COMPLETED_LEVEL_CHANCE: u32 = 75;
+const LUCK: &str = "Luck";
+const STRONG_MINDED: &str = "Strong-minded";
+const HARDY: &str = "Hardy";
+const PERSONALITY_TRAITS: usize = 2;
+
+pub struct RandomCharacterGenerator<R: RandomProvider> {
+    random_provider: R,
+    roll_income: bool,
+    roll_details: bool,
+    roll_personality: bool,
+    prune_trappings: bool,
+    pack_trappings: bool,
+    gender: Option<Gender>,
+    name: Option<String>,
+}
+
+impl<R: RandomProvider> RandomCharacterGenerator<R> {
+    pub fn new(random_provider: R) -> Self {
+        RandomCharacterGenerator { random_provider, roll_income: false, roll_details: false, roll_personality: false,
+                                   prune_trappings: false, pack_trappings: false, gender: None, name: None }
+    }
+
+    // Adds coin earned at the status of the last career level to the trappings
+    pub fn with_income(self) -> Self {
+        RandomCharacterGenerator { roll_income: true, ..self }
+    }
+
+    // Rolls age, height, colouring, birthplace and marks on the species tables
+    pub fn with_details(self) -> Self {
+        RandomCharacterGenerator { roll_details: true, ..self }
+    }
+
+    // Rolls roleplaying hooks on the tables of the last career's class
+    pub fn with_personality(self) -> Self {
+        RandomCharacterGenerator { roll_personality: true, ..self }
+    }
+
+    // Drops the heaviest loose gear until the NPC is no longer overloaded
+    pub fn with_pruning(self) -> Self {
+        RandomCharacterGenerator { prune_trappings: true, ..self }
+    }
+
+    // Puts loose gear into the NPC's containers as long as they have room
+    pub fn with_packing(self) -> Self {
+        RandomCharacterGenerator { pack_trappings: true, ..self }
+    }
+
+    // Names are drawn for the given gender instead of a random female or male one
+    pub fn with_gender(self, gender: Gender) -> Self {
+        RandomCharacterGenerator { gender: Some(gender), ..self }
+    }
+
+    pub fn with_name(self, name: &str) -> Self {
+        RandomCharacterGenerator { name: Some(name.to_string()), ..self }
+    }
+
+    // Rolls a new NPC of the same careers and species and takes the given sections from it, everything else is kept
+    pub fn reroll(&self, character: &Character, sections: &[CharacterSection], professions: &[Profession], species: &Species) -> Character {
+        let rolled = self.generate(professions, species);
+        let mut character = character.clone();
+        sections.iter().for_each(|section| section.copy(&rolled, &mut character));
+        Self::calculate_derived_values(&mut character);
+        if self.prune_trappings {
+            Self::prune_trappings(&mut character);
+        }
+        character
+    }
+
+    // Rolls one characteristic of the species again, advances bought on it are kept
+    pub fn reroll_characteristic(&self, character: &mut Character, attribute: BaseAttribute, species: &Species) {
+        if let (Some(base), Some(value)) = (species.characteristic(attribute), character.characteristic_mut(attribute)) {
+            value.initial = self.random_provider.generate(base);
+        }
+        Self::calculate_derived_values(character);
+    }
+
+    // Talent taken outside of the careers, e.g. picked by the GM. Taking it again raises its level
+    pub fn learn_talent(&self, character: &mut Character, talent: &Talent) {
+        let talent = self.specialise_talent(character, talent);
+        Self::add_talent(character, &talent);
+        Self::calculate_derived_values(character);
+    }
+
+    pub fn learn_skill(&self, character: &mut Character, skill: &SkillDefinition, advances: u32) {
+        let name = self.specialise_skill(character, skill);
+        Self::add_skill(character, &name, skill.base_attribute, advances);
+    }
+
+    fn roll(&self, species: &Species) -> Character {
+        let rp = &self.random_provider;
+        Character {
+            species: species.name.clone(),
+            weapon_skill: StatValue::new(rp.generate(&species.weapon_skill)),
+            ballistic_skill: StatValue::new(rp.generate(&species.ballistic_skill)),
+            strength: StatValue::new(rp.generate(&species.strength)),
+            toughness: StatValue::new(rp.generate(&species.toughness)),
+            initiative: StatValue::new(rp.generate(&species.initiative)),
+            agility: StatValue::new(rp.generate(&species.agility)),
+            dexterity: StatValue::new(rp.generate(&species.dexterity)),
+            intelligence: StatValue::new(rp.generate(&species.intelligence)),
+            willpower: StatValue::new(rp.generate(&species.willpower)),
+            fellowship: StatValue::new(rp.generate(&species.fellowship)),
+            movement: species.size.movement(species.movement),
+            size: species.size,
+            fate: species.fate,
+            resilience: species.resilience,
+            trappings: species.trappings.clone(),
+            ..Character::default()
+        }
+    }
+
+    fn add_species_skills(&self, character: &mut Character, species: &Species) {
+        let mut pool: Vec<&SkillDefinition> = species.possible_skills.iter().collect();
+        for advances in SPECIES_SKILL_ADVANCES {
+            let Some(index) = self.random_provider.pick_index(pool.len()) else { break };
+            let skill = pool.remove(index);
+            let name = self.specialise_skill(character, skill);
+            Self::add_skill(character, &name, skill.base_attribute, advances);
+        }
+    }
+
+    fn add_species_talents(&self, character: &mut Character, species: &Species) {
+        for alternatives in species.talents.iter() {
+            if let Some(index) = self.random_provider.pick_index(alternatives.len()) {
+                let talent = self.specialise_talent(character, &alternatives[index]);
+                Self::add_talent(character, &talent);
+            }
+        }
+
+        let mut pool: Vec<&Talent> = species.possible_talents
+                                            .iter()
+                                            .filter(|talent| character.talent_level(&talent.name) == 0)
+                                            .collect();
+        for _ in 0..species.random_talents {
+            let Some(index) = self.random_provider.pick_index(pool.len()) else { break };
+            let talent = self.specialise_talent(character, pool.remove(index));
+            Self::add_talent(character, &talent);
+        }
+    }
+
+    // Every completed level advances everything the career offered so far, switching careers starts over unless the career
+    // was taken before. A career may be left before its level is completed, with only part of the level's advances bought
+    // and no talent taken
+    fn add_professions(&self, character: &mut Character, professions: &[Profession]) {
+        let mut career_characteristics: Vec<BaseAttribute> = vec![];
+        let mut career_skills: Vec<(String, BaseAttribute)> = vec![];
+        let mut current_career: Option<&str> = None;
+
+        for (index, profession) in professions.iter().enumerate() {
+            if current_career != Some(profession.name.as_str()) {
+                current_career = Some(profession.name.as_str());
+                let earlier_levels = professions[..index].iter().filter(|earlier| earlier.name == profession.name);
+                career_characteristics = earlier_levels.clone().flat_map(|earlier| earlier.characteristics.iter().copied()).collect();
+                career_skills.clear();
+                earlier_levels.for_each(|earlier| self.add_career_skills(character, &mut career_skills, &earlier.skills));
+                profession.class.trappings.iter().for_each(|trapping| Self::add_trapping(character, trapping));
+                character.career_history.push(CareerStep { career: profession.name.clone(), level: 0, title: String::new(), completed: true });
+            }
+            let leaving = professions.get(index + 1).is_some_and(|next| next.name != profession.name);
+            let completed = !leaving || self.random_provider.generate(&GenerationBase { base_value: 0, dices: vec![100] }) <= COMPLETED_LEVEL_CHANCE;
+            if let Some(step) = character.career_history.last_mut() {
+                step.level = profession.profession_level;
+                step.title = profession.title.clone();
+                step.completed = completed;
+            }
+            career_characteristics.extend(profession.characteristics.iter().copied());
+            self.add_career_skills(character, &mut career_skills, &profession.skills);
+
+            let advances = match completed {
+                true => CAREER_LEVEL_ADVANCES,
+                false => self.random_provider.generate(&GenerationBase { base_value: 0, dices: vec![CAREER_LEVEL_ADVANCES - 1] }),
+            };
+            for characteristic in career_characteristics.iter() {
+                character.add_characteristic_advances(*characteristic, advances);
+            }
+            for (name, base_attribute) in career_skills.iter() {
+                Self::add_skill(character, name, *base_attribute, advances);
+            }
+
+            // spellcasting talents are what the career is about, so they are always granted
+            let (magic, talents): (Vec<Talent>, Vec<Talent>) = profession.talents
+                                                                         .iter()
+                                                                         .map(Talent::from)
+                                                                         .filter(|talent| character.talent_level(&talent.name) < talent.max_level)
+                                                                         .partition(|talent| talent.magic.is_some());
+            magic.iter().for_each(|talent| Self::add_talent(character, talent));
+            if let Some(index) = self.random_provider.pick_index(talents.len()).filter(|_| completed) {
+                let talent = self.specialise_talent(character, &talents[index]);
+                Self::add_talent(character, &talent);
+            }
+            profession.trappings.iter().for_each(|trapping| Self::add_trapping(character, trapping));
+        }
+    }
+
+    // An "(Any)" skill may turn out to be one the career already offers, which is advanced only once per level
+    fn add_career_skills(&self, character: &Character, career_skills: &mut Vec<(String, BaseAttribute)>, skills: &[SkillDefinition]) {
+        for skill in skills {
+            let name = self.specialise_skill(character, skill);
+            if !career_skills.iter().any(|(known, _)| *known == name) {
+                career_skills.push((name, skill.base_attribute));
+            }
+        }
+    }
+
+    // "(Any)" skills are taken as a specialisation the character already has, otherwise as a random one
+    fn specialise_skill(&self, character: &Character, skill: &SkillDefinition) -> String {
+        self.specialise(&skill.name, &skill.specialisations, character.skills.iter().map(|known| known.name.as_str()))
+    }
+
+    // Same for talents, leaving out the ones already at their highest level. Spellcasting talents get their lore in learn_spells
+    fn specialise_talent(&self, character: &Character, talent: &Talent) -> Talent {
+        if talent.magic.is_some() {
+            return talent.clone();
+        }
+        let known = character.talents.iter().filter(|known| known.level < known.max_level).map(|known| known.name.as_str());
+        Talent { name: self.specialise(&talent.name, &talent.specialisations, known), ..talent.clone() }
+    }
+
+    fn specialise<'a>(&self, name: &str, specialisations: &[String], mut known: impl Iterator<Item = &'a str>) -> String {
+        if !specialisation::is_any(name) {
+            return name.to_string();
+        }
+        known.find(|known| specialisation::base_name(known) == specialisation::base_name(name) && !specialisation::is_any(known))
+             .map(str::to_string)
+             .or_else(|| self.random_provider.pick_index(specialisations.len()).map(|index| specialisation::specialised(name, &specialisations[index])))
+             .unwrap_or_else(|| name.to_string())
+    }
+
+    // "(Any)" lores are chosen at random, preferring a deity or lore the character already follows
+    fn learn_spells(&self, character: &mut Character) {
+        let mut known_lores: Vec<Lore> = vec![];
+        for index in 0..character.talents.len() {
+            let talent = character.talents[index].clone();
+            let Some(grant) = &talent.magic else { continue };
+            let lore = match talent.specialisation().filter(|_| !talent.is_any_specialisation()) {
+                Some(name) => grant.lore(name),
+                None => character.talents
+                                 .iter()
+                                 .filter_map(|other| other.specialisation())
+                                 .find_map(|name| grant.lore(name))
+                                 .or_else(|| self.random_provider.pick_index(grant.lores.len()).map(|index| &grant.lores[index])),
+            };
+            let Some(lore) = lore else { continue };
+            let talent = if talent.is_any_specialisation() { talent.specialised(&lore.name) } else { talent.clone() };
+
+            let mut pool: Vec<usize> = (0..lore.spells.len()).collect();
+            let mut known = vec![];
+            for _ in 0..grant.known.count(character).min(lore.spells.len() as u32) {
+                let Some(index) = self.random_provider.pick_index(pool.len()) else { break };
+                known.push(pool.remove(index));
+            }
+            known.sort();
+            let spells: Vec<Spell> = known.into_iter().map(|index| lore.spells[index].clone()).collect();
+            known_lores.push(Lore { name: talent.name.clone(), spells });
+            character.talents[index] = talent;
+        }
+        character.spells = known_lores;
+    }
+
+    fn distribute_extra_points(&self, character: &mut Character, species: &Species) {
+        for _ in 0..species.extra_points {
+            match self.random_provider.pick_index(2) {
+                Some(0) => character.fate += 1,
+                _ => character.resilience += 1,
+            }
+        }
+    }
+
+    fn add_name(&self, character: &mut Character, professions: &[Profession], species: &Species) {
+        if let Some(name) = &self.name {
+            character.name = name.clone();
+            return;
+        }
+        let gender = self.gender.unwrap_or_else(|| match self.random_provider.pick_index(2) {
+            Some(0) => Gender::Female,
+            _ => Gender::Male,
+        });
+        let career = professions.last().map(|profession| profession.name.as_str());
+        character.name = NameGenerator::new(&self.random_provider).generate(&species.names, gender, career);
+    }
+
+    fn roll_details(&self, tables: &DetailTables) -> Details {
+        let rp = &self.random_provider;
+        let roll_on = |table: &RollTable| table.lookup(rp.generate(&table.dice())).unwrap_or_default().to_string();
+        Details {
+            age: rp.generate(&tables.age),
+            height: rp.generate(&tables.height),
+            eyes: roll_on(&tables.eyes),
+            hair: roll_on(&tables.hair),
+            birthplace: roll_on(&tables.birthplace),
+            distinguishing_mark: Some(roll_on(&tables.marks)).filter(|mark| !mark.is_empty()),
+        }
+    }
+
+    fn roll_personality(&self, tables: &PersonalityTables) -> Personality {
+        let pick = |values: &[String]| self.random_provider.pick_index(values.len()).map(|index| values[index].clone()).unwrap_or_default();
+        let mut traits_pool: Vec<&String> = tables.traits.iter().collect();
+        let mut traits = vec![];
+        for _ in 0..PERSONALITY_TRAITS {
+            let Some(index) = self.random_provider.pick_index(traits_pool.len()) else { break };
+            traits.push(traits_pool.remove(index).clone());
+        }
+        Personality {
+            traits,
+            motivation: pick(&tables.motivations),
+            short_term_ambition: pick(&tables.short_term_ambitions),
+            long_term_ambition: pick(&tables.long_term_ambitions),
+            secret: pick(&tables.secrets),
+        }
+    }
+
+    fn add_income(&self, character: &mut Character) {
+        let Some(status) = character.status else { return };
+        let count = self.random_provider.generate(&status.income());
+        if count == 0 {
+            return;
+        }
+        match character.trappings.iter_mut().find(|owned| owned.name == status.tier.coin()) {
+            Some(owned) => owned.count += count,
+            None => character.trappings.push(Trapping::new(status.tier.coin(), count)),
+        }
+    }
+
+    fn prune_trappings(character: &mut Character) {
+        while character.is_overloaded() {
+            let Some(heaviest) = character.trappings
+                                          .iter()
+                                          .enumerate()
+                                          .filter(|(_, trapping)| trapping.is_packable() && trapping.encumbrance > 0)
+                                          .max_by_key(|(_, trapping)| trapping.encumbrance)
+                                          .map(|(index, _)| index) else { break };
+            character.trappings[heaviest].count -= 1;
+            if character.trappings[heaviest].count == 0 {
+                character.trappings.remove(heaviest);
+            }
+        }
+    }
+
+    fn pack_trappings(character: &mut Character) {
+        let mut containers: Vec<(String, u32)> = character.trappings
+                                                          .iter()
+                                                          .filter(|trapping| trapping.is_container())
+                                                          .map(|trapping| (trapping.name.clone(), trapping.carries * trapping.count))
+                                                          .collect();
+        for trapping in character.trappings.iter_mut().filter(|trapping| trapping.is_packable()) {
+            let encumbrance = trapping.total_encumbrance();
+            if let Some((container, room)) = containers.iter_mut().find(|(_, room)| *room >= encumbrance) {
+                *room -= encumbrance;
+                trapping.packed_in = Some(container.clone());
+            }
+        }
+    }
+
+    fn calculate_derived_values(character: &mut Character) {
+        character.fortune = character.fate + character.talent_level(LUCK);
+        character.resolve = character.resilience + character.talent_level(STRONG_MINDED);
+        character.wounds = character.size.wounds(character.strength.bonus(), character.toughness.bonus(), character.willpower.bonus())
+                           + character.talent_level(HARDY) * character.toughness.bonus();
+    }
+
+    fn add_skill(character: &mut Character, name: &str, base_attribute: BaseAttribute, advances: u32) {
+        if !character.add_skill_advances(name, advances) {
+            character.skills.push(Skill { name: name.to_string(), advances, base_attribute });
+        }
+    }
+
+    fn add_talent(character: &mut Character, talent: &Talent) {
+        match character.talents.iter_mut().find(|known| known.name == talent.name) {
+            Some(known) => known.level = (known.level + 1).min(known.max_level),
+            None => character.talents.push(Talent { level: 1, ..talent.clone() }),
+        }
+    }
+
+    fn add_trapping(character: &mut Character, trapping: &Trapping) {
+        match character.trappings.iter_mut().find(|owned| owned.name == trapping.name) {
+            Some(owned) => owned.count = owned.count.max(trapping.count),
+            None => character.trappings.push(trapping.clone()),
+        }
+    }
+}
+
+impl<R: RandomProvider> CharacterGenerator for RandomCharacterGenerator<R> {
+    fn generate(&self, professions: &[Profession], species: &Species) -> Character {
+        let mut character = self.roll(species);
+        self.add_species_skills(&mut character, species);
+        self.add_species_talents(&mut character, species);
+        self.add_professions(&mut character, professions);
+        self.learn_spells(&mut character);
+        character.status = professions.last().map(|profession| profession.status);
+        if self.roll_income {
+            self.add_income(&mut character);
+        }
+        self.distribute_extra_points(&mut character, species);
+        Self::calculate_derived_values(&mut character);
+        self.add_name(&mut character, professions, species);
+        if self.prune_trappings {
+            Self::prune_trappings(&mut character);
+        }
+        if self.pack_trappings {
+            Self::pack_trappings(&mut character);
+        }
+        if self.roll_details {
+            character.details = species.details.as_ref().map(|tables| self.roll_details(tables));
+        }
+        if self.roll_personality {
+            character.personality = professions.last()
+                                               .and_then(|profession| profession.class.personality.as_ref())
+                                               .map(|tables| self.roll_personality(tables));
+        }
+        character
+    }
+}
+
+// Creatures take their characteristics and traits from the bestiary, with no species or career on top
+impl<R: RandomProvider> CreatureGenerator for RandomCharacterGenerator<R> {
+    fn generate_creature(&self, creature: &Creature) -> Character {
+        let rp = &self.random_provider;
+        let mut character = Character {
+            name: self.name.clone().unwrap_or_else(|| creature.name.clone()),
+            weapon_skill: StatValue::new(rp.generate(&creature.weapon_skill)),
+            ballistic_skill: StatValue::new(rp.generate(&creature.ballistic_skill)),
+            strength: StatValue::new(rp.generate(&creature.strength)),
+            toughness: StatValue::new(rp.generate(&creature.toughness)),
+            initiative: StatValue::new(rp.generate(&creature.initiative)),
+            agility: StatValue::new(rp.generate(&creature.agility)),
+            dexterity: StatValue::new(rp.generate(&creature.dexterity)),
+            intelligence: StatValue::new(rp.generate(&creature.intelligence)),
+            willpower: StatValue::new(rp.generate(&creature.willpower)),
+            fellowship: StatValue::new(rp.generate(&creature.fellowship)),
+            movement: creature.size.movement(creature.movement),
+            size: creature.size,
+            traits: creature.traits.clone(),
+            trappings: creature.trappings.clone(),
+            ..Character::default()
+        };
+
+        let mut pool: Vec<&CreatureTrait> = creature.optional_traits.iter().collect();
+        for _ in 0..creature.random_traits {
+            let Some(index) = rp.pick_index(pool.len()) else { break };
+            character.traits.push(pool.remove(index).clone());
+        }
+        Self::calculate_derived_values(&mut character);
+        if self.prune_trappings {
+            Self::prune_trappings(&mut character);
+        }
+        character
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::interfaces::resource_bag::ResourceBag;
+    use crate::models::size::Size;
+    use crate::types::game_data::GameData;
+    use crate::types::generate_npc_command_args::GenerateNpcCommandArgs;
+    use crate::types::seeded_random_provider::SeededRandomProvider;
+
+    // Every die rolls 1, so picks always take the first element
+    struct LowestRandomProvider {}
+
+    impl RandomProvider for LowestRandomProvider {
+        fn generate(&self, base: &GenerationBase) -> u32 {
+            base.base_value + base.dices.len() as u32
+        }
+
+        fn fork(&self, _stream: u64) -> Self {
+            LowestRandomProvider {}
+        }
+    }
+
+    // Every die rolls its highest face, so picks always take the last element
+    struct HighestRandomProvider {}
+
+    impl RandomProvider for HighestRandomProvider {
+        fn generate(&self, base: &GenerationBase) -> u32 {
+            base.base_value + base.dices.iter().sum::<u32>()
+        }
+
+        fn fork(&self, _stream: u64) -> Self {
+            HighestRandomProvider {}
+        }
+    }
+
+    fn generate(professions: &[&str], species: &str) -> Character {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = professions.iter().map(|id| data.get_by_id(id).unwrap()).collect();
+        let species: Species = data.get_by_id(species).unwrap();
+        RandomCharacterGenerator::new(LowestRandomProvider{}).generate(&professions, &species)
+    }
+
+    #[test]
+    fn characteristics_are_rolled_from_species() {
+        let character = generate(&[], "dwarf");
+        assert_eq!(character.weapon_skill, StatValue::new(32));
+        assert_eq!(character.agility, StatValue::new(12));
+        assert_eq!(character.willpower, StatValue::new(42));
+        assert_eq!(character.movement, 3);
+    }
+
+    #[test]
+    fn species_skills_get_starting_advances() {
+        let character = generate(&[], "human");
+        let advances: Vec<(&str, u32)> = character.skills.iter().map(|skill| (skill.name.as_str(), skill.advances)).collect();
+        assert_eq!(advances, vec![("Animal Care", 5), ("Charm", 5), ("Cool", 5), ("Evaluate", 3), ("Gossip", 3), ("Haggle", 3)]);
+    }
+
+    #[test]
+    fn species_talents_include_random_talents() {
+        let character = generate(&[], "human");
+        let talents: Vec<&str> = character.talents.iter().map(|talent| talent.name.as_str()).collect();
+        assert_eq!(talents, vec!["Doomed", "Savvy", "Acute Sense (Sight)", "Ambidextrous", "Animal Affinity"]);
+    }
+
+    #[test]
+    fn completed_levels_are_cumulative() {
+        let character = generate(&["soldier_1", "soldier_2"], "human");
+        assert_eq!(character.weapon_skill, StatValue{initial: 22, advances: 10});
+        assert_eq!(character.ballistic_skill, StatValue{initial: 22, advances: 5});
+        assert_eq!(character.skills.iter().find(|skill| skill.name == "Athletics").unwrap().advances, 10);
+        assert_eq!(character.skills.iter().find(|skill| skill.name == "Gamble").unwrap().advances, 5);
+        // species advances stack with career advances
+        assert_eq!(character.skills.iter().find(|skill| skill.name == "Cool").unwrap().advances, 15);
+        assert!(character.trappings.iter().any(|trapping| trapping.name == "Hand Weapon"));
+        assert!(character.trappings.iter().any(|trapping| trapping.name == "Helmet"));
+    }
+
+    #[test]
+    fn career_history_follows_the_career_order() {
+        let character = generate(&["soldier_1", "soldier_2", "soldier_3", "outlaw_1", "outlaw_2"], "human");
+        assert_eq!(character.formatted_career_history(), "Soldier 3 (Sergeant) → Outlaw 2");
+        assert_eq!(character.career_switch_cost(), 100);
+        assert!(generate(&[], "human").career_history.is_empty());
+    }
+
+    #[test]
+    fn returning_to_a_career_keeps_its_completed_levels() {
+        let returned = generate(&["soldier_1", "soldier_2", "outlaw_1", "soldier_3"], "human");
+        let stayed = generate(&["soldier_1", "soldier_2", "soldier_3"], "human");
+        assert_eq!(returned.formatted_career_history(), "Soldier 2 → Outlaw 1 (Brigand) → Soldier 3 (Sergeant)");
+        assert_eq!(returned.career_switch_cost(), 200);
+        let melee = |character: &Character| character.skills.iter().find(|skill| skill.name == "Melee (Basic)").map(|skill| skill.advances);
+        assert!(melee(&returned) >= melee(&stayed));
+    }
+
+    #[test]
+    fn any_specialisation_prefers_the_one_already_known() {
+        let character = generate(&["villager_1", "villager_2"], "human");
+        let advances = |name: &str| character.skills.iter().find(|skill| skill.name == name).map(|skill| skill.advances);
+        assert_eq!(advances("Melee (Brawling)"), Some(10));
+        assert_eq!(advances("Trade (Apothecary)"), Some(5));
+        assert_eq!(advances("Melee (Any)"), None);
+    }
+
+    #[test]
+    fn no_any_specialisation_is_left() {
+        let data = GameData::embedded();
+        let species: Species = data.get_by_id("human").unwrap();
+        for careers in [["soldier_4"], ["hunter_4"], ["guard_4"], ["scholar_4"], ["merchant_4"], ["villager_4"]] {
+            let professions: Vec<Profession> = GenerateNpcCommandArgs::profession_ids(careers.into_iter()).iter().map(|id| data.get_by_id(id).unwrap()).collect();
+            for seed in 0..10 {
+                let character = RandomCharacterGenerator::new(SeededRandomProvider::new(seed)).generate(&professions, &species);
+                let names = character.skills.iter().map(|skill| &skill.name).chain(character.talents.iter().map(|talent| &talent.name));
+                assert!(names.clone().all(|name| !specialisation::is_any(name)), "{:?} left unresolved", names.collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn career_left_before_completing_its_level() {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = ["soldier_1", "soldier_2", "outlaw_1"].iter().map(|id| data.get_by_id(id).unwrap()).collect();
+        let species: Species = data.get_by_id("human").unwrap();
+        let character = RandomCharacterGenerator::new(HighestRandomProvider{}).generate(&professions, &species);
+        assert_eq!(character.career_history.iter().map(|step| step.completed).collect::<Vec<bool>>(), vec![false, true]);
+        assert_eq!(character.career_switch_cost(), 200);
+        // only part of the advances of the level left were bought, and no talent was taken for it
+        assert_eq!(character.ballistic_skill.advances, CAREER_LEVEL_ADVANCES - 1);
+        assert!(["Drilled", "Etiquette (Soldiers)", "Rapid Reload", "Shieldsman"].iter().all(|talent| character.talent_level(talent) == 0));
+
+        let completed = generate(&["soldier_1", "soldier_2", "outlaw_1"], "human");
+        assert_eq!(completed.career_switch_cost(), 100);
+    }
+
+    #[test]
+    fn one_talent_per_career_level() {
+        let character = generate(&["soldier_1", "soldier_2"], "dwarf");
+        assert_eq!(character.talent_level("Diceman"), 1);
+        assert_eq!(character.talent_level("Drilled"), 1);
+    }
+
+    #[test]
+    fn fate_and_resilience_with_extra_points() {
+        // lowest rolls put every extra point into Fate
+        let character = generate(&[], "human");
+        assert_eq!((character.fate, character.fortune, character.resilience, character.resolve, character.corruption), (5, 5, 1, 1, 0));
+    }
+
+    #[test]
+    fn luck_and_strong_minded_raise_fortune_and_resolve() {
+        let mut species: Species = GameData::embedded().get_by_id("dwarf").unwrap();
+        let strong_minded = species.talents[3][1].clone();
+        let luck = species.possible_talents.iter().find(|talent| talent.name == "Luck").unwrap().clone();
+        species.talents = vec![vec![strong_minded], vec![luck]];
+
+        let character = RandomCharacterGenerator::new(LowestRandomProvider{}).generate(&[], &species);
+        assert_eq!((character.fate, character.fortune, character.resilience, character.resolve), (2, 3, 2, 3));
+    }
+
+    #[test]
+    fn status_comes_from_last_career_level() {
+        let character = generate(&["soldier_1", "soldier_2"], "human");
+        assert_eq!(character.status.map(|status| status.to_string()), Some("Silver 3".to_string()));
+        assert!(!character.trappings.iter().any(|trapping| trapping.name == "Silver Shillings"));
+        assert_eq!(generate(&[], "human").status, None);
+    }
+
+    #[test]
+    fn income_is_rolled_from_status() {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = ["soldier_1", "soldier_2"].iter().map(|id| data.get_by_id(id).unwrap()).collect();
+        let species: Species = data.get_by_id("human").unwrap();
+        let character = RandomCharacterGenerator::new(LowestRandomProvider{}).with_income().generate(&professions, &species);
+        // Silver 3 rolls 3d10 shillings
+        let coin = character.trappings.iter().find(|trapping| trapping.name == "Silver Shillings").unwrap();
+        assert_eq!(coin.count, 3);
+    }
+
+    #[test]
+    fn name_is_drawn_from_species_names() {
+        // lowest rolls give a female name and the first surname form
+        assert_eq!(generate(&["soldier_1"], "human").name, "Agnetha Bauer");
+        assert_eq!(generate(&[], "dwarf").name, "Asta Brightaxe");
+    }
+
+    #[test]
+    fn name_override_and_gender() {
+        let species: Species = GameData::embedded().get_by_id("halfling").unwrap();
+        let generator = RandomCharacterGenerator::new(LowestRandomProvider{});
+        assert_eq!(generator.with_gender(Gender::Male).generate(&[], &species).name, "Adam Ashfield");
+        let generator = RandomCharacterGenerator::new(LowestRandomProvider{});
+        assert_eq!(generator.with_name("Lumpin Croop").generate(&[], &species).name, "Lumpin Croop");
+    }
+
+    #[test]
+    fn details_are_rolled_from_species_tables() {
+        let species: Species = GameData::embedded().get_by_id("human").unwrap();
+        assert_eq!(RandomCharacterGenerator::new(LowestRandomProvider{}).generate(&[], &species).details, None);
+
+        let character = RandomCharacterGenerator::new(LowestRandomProvider{}).with_details().generate(&[], &species);
+        assert_eq!(character.details, Some(Details { age: 16, height: 59, eyes: "Pale Grey".into(), hair: "White Blond".into(),
+                                                     birthplace: "Altdorf".into(), distinguishing_mark: None }));
+    }
+
+    #[test]
+    fn personality_is_rolled_from_class_tables() {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = vec![data.get_by_id("thief_1").unwrap()];
+        let species: Species = data.get_by_id("human").unwrap();
+        let character = RandomCharacterGenerator::new(LowestRandomProvider{}).with_personality().generate(&professions, &species);
+        assert_eq!(character.personality, Some(Personality { traits: vec!["Boastful".into(), "Cautious".into()], motivation: "Wealth".into(),
+                                                             short_term_ambition: "Pull off a daring heist".into(),
+                                                             long_term_ambition: "Run the city's underworld".into(),
+                                                             secret: "Informs for the watch".into() }));
+        // without a career there is no class to take the tables from
+        assert_eq!(RandomCharacterGenerator::new(LowestRandomProvider{}).with_personality().generate(&[], &species).personality, None);
+    }
+
+    fn trapping(name: &str, count: u32, encumbrance: u32, carries: u32) -> Trapping {
+        Trapping { encumbrance, carries, ..Trapping::new(name, count) }
+    }
+
+    #[test]
+    fn pruning_drops_heaviest_loose_gear() {
+        let mut character = Character {
+            strength: StatValue::new(20),
+            toughness: StatValue::new(20),
+            trappings: vec![trapping("Rope", 2, 1, 0), trapping("Pole", 1, 2, 0), Trapping { worn: true, ..trapping("Clothing", 1, 1, 0) }],
+            ..Character::default()
+        };
+        RandomCharacterGenerator::<LowestRandomProvider>::prune_trappings(&mut character);
+        let left: Vec<(&str, u32)> = character.trappings.iter().map(|trapping| (trapping.name.as_str(), trapping.count)).collect();
+        assert_eq!(left, vec![("Rope", 2), ("Clothing", 1)]);
+        assert!(!character.is_overloaded());
+    }
+
+    #[test]
+    fn packing_fills_containers() {
+        let mut character = Character {
+            trappings: vec![trapping("Sling Bag", 1, 1, 2), trapping("Rope", 1, 1, 0), trapping("Book", 1, 1, 0), trapping("Blanket", 1, 1, 0)],
+            ..Character::default()
+        };
+        RandomCharacterGenerator::<LowestRandomProvider>::pack_trappings(&mut character);
+        let packed: Vec<Option<&str>> = character.trappings.iter().map(|trapping| trapping.packed_in.as_deref()).collect();
+        assert_eq!(packed, vec![None, Some("Sling Bag"), Some("Sling Bag"), None]);
+    }
+
+    #[test]
+    fn same_seed_same_character() {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = vec![data.get_by_id("thief_2").unwrap()];
+        let species: Species = data.get_by_id("halfling").unwrap();
+        let generate = || RandomCharacterGenerator::new(SeededRandomProvider::new(42)).with_details().generate(&professions, &species);
+        let (first, second) = (generate(), generate());
+
+        assert_eq!(first.name, second.name);
+        assert_eq!(first.details, second.details);
+        assert_eq!(first.weapon_skill, second.weapon_skill);
+        assert_eq!(first.talents.iter().map(|talent| &talent.name).collect::<Vec<_>>(), second.talents.iter().map(|talent| &talent.name).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn spells_are_learned_from_career_talents() {
+        let character = generate(&["wizard_1", "wizard_2"], "human");
+        assert_eq!(character.talent_level("Petty Magic"), 1);
+        assert_eq!(character.talent_level("Arcane Magic (Fire)"), 1);
+        let lores: Vec<(&str, usize)> = character.spells.iter().map(|lore| (lore.name.as_str(), lore.spells.len())).collect();
+        assert_eq!(lores, vec![("Petty Magic", character.willpower.bonus() as usize),
+                               ("Arcane Magic (Fire)", character.intelligence.bonus().min(4) as usize)]);
+        assert_eq!(character.spells[0].spells[0].name, "Animal Friend");
+
+        assert!(generate(&["soldier_1"], "human").spells.is_empty());
+    }
+
+    #[test]
+    fn priest_invokes_the_deity_it_blesses() {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = vec![data.get_by_id("priest_1").unwrap(), data.get_by_id("priest_2").unwrap()];
+        let species: Species = data.get_by_id("human").unwrap();
+        let character = RandomCharacterGenerator::new(SeededRandomProvider::new(7)).generate(&professions, &species);
+        let bless = character.talents.iter().find(|talent| specialisation::base_name(&talent.name) == "Bless").unwrap();
+        let invoke = character.talents.iter().find(|talent| specialisation::base_name(&talent.name) == "Invoke").unwrap();
+        assert_eq!(bless.specialisation(), invoke.specialisation());
+        assert_eq!(character.spells.iter().map(|lore| lore.spells.len()).collect::<Vec<usize>>(), vec![4, 1]);
+    }
+
+    #[test]
+    fn creatures_are_rolled_from_the_bestiary() {
+        let wolf: Creature = GameData::embedded().get_by_id("wolf").unwrap();
+        let character = RandomCharacterGenerator::new(LowestRandomProvider{}).generate_creature(&wolf);
+        assert_eq!(character.name, "Wolf");
+        assert_eq!((character.weapon_skill.current(), character.dexterity.current(), character.movement), (27, 0, 9));
+        let traits: Vec<String> = character.traits.iter().map(|creature_trait| character.formatted_trait(creature_trait)).collect();
+        assert_eq!(traits, vec!["Bestial", "Bite +5", "Night Vision", "Skittish", "Stride", "Tracker", "Armour 1"]);
+        assert!(character.talents.is_empty() && character.skills.is_empty());
+        // SB 2 + 2 * TB 2 + WPB 2
+        assert_eq!(character.wounds, 8);
+    }
+
+    #[test]
+    fn wounds_follow_size() {
+        let data = GameData::embedded();
+        let halfling = generate(&[], "halfling");
+        // 2 * TB 2 + WPB 3, no Strength Bonus for Small
+        assert_eq!((halfling.size, halfling.wounds), (Size::Small, 7));
+
+        let bear: Creature = data.get_by_id("bear").unwrap();
+        let bear = RandomCharacterGenerator::new(LowestRandomProvider{}).generate_creature(&bear);
+        // (SB 4 + 2 * TB 4 + WPB 2) * 2
+        assert_eq!((bear.size, bear.wounds), (Size::Large, 28));
+    }
+
+    #[test]
+    fn movement_follows_size() {
+        let data = GameData::embedded();
+        let species_movement = |id: &str| { let species: Species = data.get_by_id(id).unwrap(); (species.movement, generate(&[], id).movement) };
+        // Movement 4 of an Average build, one less for Small and one more for Large
+        assert_eq!(species_movement("human"), (4, 4));
+        assert_eq!(species_movement("halfling"), (4, 3));
+        assert_eq!(species_movement("ogre"), (5, 6));
+
+        let creature_movement = |id: &str| { let creature: Creature = data.get_by_id(id).unwrap(); (creature.movement, RandomCharacterGenerator::new(LowestRandomProvider{}).generate_creature(&creature).movement) };
+        assert_eq!(creature_movement("giant_rat"), (7, 6));
+        assert_eq!(creature_movement("bear"), (3, 4));
+    }
+
+    #[test]
+    fn wounds_are_derived_from_bonuses() {
+        let character = generate(&[], "dwarf");
+        // SB 2 + 2 * TB 3 + WPB 4
+        assert_eq!(character.wounds, 12);
+    }
+
+    #[test]
+    fn rerolled_characteristic_keeps_advances() {
+        let data = GameData::embedded();
+        let dwarf: Species = data.get_by_id("dwarf").unwrap();
+        let mut character = generate(&["soldier_1"], "dwarf");
+        let advances = character.toughness.advances;
+        RandomCharacterGenerator::new(SeededRandomProvider::new(4)).reroll_characteristic(&mut character, BaseAttribute::Toughness, &dwarf);
+        assert_eq!(character.toughness.advances, advances);
+        assert!((32..=50).contains(&character.toughness.initial));
+        assert_eq!(character.wounds, character.strength.bonus() + 2 * character.toughness.bonus() + character.willpower.bonus());
+
+        let generator = RandomCharacterGenerator::new(LowestRandomProvider{});
+        generator.learn_talent(&mut character, &data.get_by_id("Hardy").unwrap());
+        assert_eq!(character.wounds, character.strength.bonus() + 3 * character.toughness.bonus() + character.willpower.bonus());
+        generator.learn_skill(&mut character, &data.get_by_id("Lore (Engineering)").unwrap(), 5);
+        assert_eq!(character.skills.last().map(|skill| (skill.name.as_str(), skill.advances)), Some(("Lore (Engineering)", 5)));
+    }
+
+    #[test]
+    fn reroll_keeps_the_other_sections() {
+        let data = GameData::embedded();
+        let professions: Vec<Profession> = vec![data.get_by_id("soldier_1").unwrap(), data.get_by_id("soldier_2").unwrap()];
+        let species: Species = data.get_by_id("human").unwrap();
+        let character = RandomCharacterGenerator::new(LowestRandomProvider{}).generate(&professions, &species);
+        let rerolled = RandomCharacterGenerator::new(SeededRandomProvider::new(12))
+                           .reroll(&character, &[CharacterSection::Characteristics, CharacterSection::Name], &professions, &species);
+        let names = |character: &Character| character.talents.iter().map(|talent| talent.name.clone()).collect::<Vec<String>>();
+        assert_eq!(names(&rerolled), names(&character));
+        assert_eq!(rerolled.trappings.len(), character.trappings.len());
+        assert_ne!(rerolled.weapon_skill, character.weapon_skill);
+        assert_eq!(rerolled.weapon_skill.advances, character.weapon_skill.advances);
+        assert_eq!(rerolled.wounds, rerolled.strength.bonus() + 2 * rerolled.toughness.bonus() + rerolled.willpower.bonus());
+    }
+}
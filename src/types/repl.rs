@@ -0,0 +1,282 @@
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+
+use crate::models::{base_attribute::BaseAttribute, character::Character, character_section::CharacterSection, consts::{MAX_PROFESSION_LEVEL, MIN_PROFESSION_LEVEL},
+                    profession::Profession, species::Species};
+use super::character_attributes_iter::CharacterAttributesIter;
+use super::command::CommandError;
+use super::game_data::GameData;
+use super::generate_npc_command_args::{GenerateNpcCommandArgs, RANDOM_CAREER, RANDOM_SPECIES};
+use super::npc_library::NpcLibrary;
+
+pub const REPL_PROMPT: &str = "npc> ";
+// Advances of a skill added without giving them, as many as a career level buys
+const DEFAULT_SKILL_ADVANCES: u32 = 5;
+const COMMANDS: [(&str, &str); 12] = [
+    ("gen", "gen <professions...> <species>  generate a new NPC"),
+    ("reroll", "reroll [characteristic|sections...]  roll the NPC again but its locked sections, or only the given parts"),
+    ("lock", "lock <sections...>  keep sections when rerolling"),
+    ("unlock", "unlock [sections...]  reroll sections again, all of them without any"),
+    ("add-talent", "add-talent <talent>  give the NPC a talent"),
+    ("add-skill", "add-skill <skill> [advances]  give the NPC advances in a skill"),
+    ("show", "show  print the NPC again"),
+    ("save", "save <id>  keep the NPC in the library"),
+    ("load", "load <id>  continue working on an NPC of the library"),
+    ("help", "help  list the commands"),
+    ("quit", "quit  leave the session"),
+    ("exit", "exit  leave the session"),
+];
+
+// One line typed during an interactive session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplCommand {
+    Generate { careers: Vec<String>, species: String },
+    // no sections stand for everything that is not locked
+    Reroll(Vec<CharacterSection>),
+    RerollCharacteristic(BaseAttribute),
+    Lock(Vec<CharacterSection>),
+    Unlock(Vec<CharacterSection>),
+    AddTalent(String),
+    AddSkill(String, u32),
+    Show,
+    Save(String),
+    Load(String),
+    Help,
+    Quit,
+}
+
+impl ReplCommand {
+    // Empty lines are no command at all
+    pub fn parse(line: &str) -> Result<Option<Self>, CommandError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((command, args)) = words.split_first() else { return Ok(None) };
+
+        let command = match *command {
+            "gen" => {
+                let Some((species, careers)) = args.split_last().filter(|(_, careers)| !careers.is_empty()) else {
+                    Err(CommandError::TooFewArguments(args.len(), 2))?
+                };
+                let npc_args: Vec<String> = vec![careers.join(" "), species.to_string()];
+                GenerateNpcCommandArgs(&npc_args).validate_professions(None)?
+                                                 .validate_species(None)?;
+                ReplCommand::Generate { careers: careers.iter().map(|career| career.to_string()).collect(), species: species.to_string() }
+            }
+            "reroll" => match (args, args.first().and_then(|name| Self::characteristic(name))) {
+                ([_], Some(characteristic)) => ReplCommand::RerollCharacteristic(characteristic),
+                (sections, _) => ReplCommand::Reroll(Self::sections(sections)?),
+            },
+            "lock" if args.is_empty() => Err(CommandError::TooFewArguments(0, 1))?,
+            "lock" => ReplCommand::Lock(Self::sections(args)?),
+            "unlock" => ReplCommand::Unlock(Self::sections(args)?),
+            "add-talent" if args.is_empty() => Err(CommandError::TooFewArguments(0, 1))?,
+            "add-talent" => ReplCommand::AddTalent(args.join(" ")),
+            "add-skill" => {
+                let (skill, advances) = match args.split_last().map(|(advances, skill)| (skill, advances.parse::<u32>())) {
+                    Some((skill, Ok(advances))) if !skill.is_empty() => (skill, advances),
+                    _ => (args, DEFAULT_SKILL_ADVANCES),
+                };
+                if skill.is_empty() {
+                    Err(CommandError::TooFewArguments(0, 1))?
+                }
+                ReplCommand::AddSkill(skill.join(" "), advances)
+            }
+            "show" => ReplCommand::Show,
+            "save" | "load" => match args {
+                [] => Err(CommandError::TooFewArguments(0, 1))?,
+                [id] if !NpcLibrary::is_valid_id(id) => Err(CommandError::InvalidArguments)?,
+                [id] if *command == "save" => ReplCommand::Save(id.to_string()),
+                [id] => ReplCommand::Load(id.to_string()),
+                _ => Err(CommandError::TooManyArguments(args.len(), 1))?,
+            },
+            "help" => ReplCommand::Help,
+            "quit" | "exit" => ReplCommand::Quit,
+            _ => Err(CommandError::InvalidCommand)?,
+        };
+        Ok(Some(command))
+    }
+
+    pub fn help() -> String {
+        COMMANDS.iter().map(|(_, description)| format!("\t{}\n", description)).collect()
+    }
+
+    // Characteristics are written like "strength", "weapon_skill" or their abbreviation "ws"
+    fn characteristic(name: &str) -> Option<BaseAttribute> {
+        let name = name.to_lowercase();
+        CharacterAttributesIter::new(&Character::default())
+            .filter(|attribute| attribute.attribute.is_characteristic())
+            .find(|attribute| attribute.name.to_lowercase().replace(' ', "_") == name || attribute.short_name.to_lowercase() == name)
+            .map(|attribute| attribute.attribute)
+    }
+
+    fn characteristic_names() -> Vec<String> {
+        CharacterAttributesIter::new(&Character::default())
+            .filter(|attribute| attribute.attribute.is_characteristic())
+            .map(|attribute| attribute.name.to_lowercase().replace(' ', "_"))
+            .collect()
+    }
+
+    fn sections(args: &[&str]) -> Result<Vec<CharacterSection>, CommandError> {
+        args.iter().map(|section| section.parse().map_err(|_| CommandError::InvalidArguments)).collect()
+    }
+}
+
+// NPC being worked on in an interactive session and what it was generated from
+#[derive(Debug, Default)]
+pub struct ReplSession {
+    pub character: Option<Character>,
+    // as typed, "random" is rolled again on every full reroll
+    pub careers: Vec<String>,
+    pub species: String,
+    // what they were rolled into, sections are rerolled on them
+    pub professions: Vec<Profession>,
+    pub rolled_species: Option<Species>,
+    pub locked: Vec<CharacterSection>,
+    // seed of the last roll, gone once the NPC is changed by hand or rerolled in parts
+    pub seed: Option<u64>,
+}
+
+impl ReplSession {
+    pub fn formatted_locked(&self) -> String {
+        match self.locked.is_empty() {
+            true => "Nothing locked".to_string(),
+            false => format!("Locked: {}", self.locked.iter().map(|section| section.to_str()).collect::<Vec<&str>>().join(", ")),
+        }
+    }
+}
+
+// Tab completion of commands and of the careers, species, characteristics, skills and talents they take
+pub struct ReplHelper {
+    professions: Vec<String>,
+    species: Vec<String>,
+    characteristics: Vec<String>,
+    sections: Vec<String>,
+    skills: Vec<String>,
+    talents: Vec<String>,
+}
+
+impl ReplHelper {
+    pub fn new(game_data: &GameData) -> Self {
+        // same levels as the command line accepts
+        let levels = MIN_PROFESSION_LEVEL..=MAX_PROFESSION_LEVEL;
+        let professions = std::iter::once(RANDOM_CAREER)
+            .chain(game_data.career_ids())
+            .flat_map(|career| levels.clone().map(move |level| format!("{}_{}", career, level)))
+            .collect();
+        ReplHelper {
+            professions,
+            species: std::iter::once(RANDOM_SPECIES).chain(game_data.species_ids()).map(str::to_string).collect(),
+            characteristics: ReplCommand::characteristic_names(),
+            sections: CharacterSection::iterator().map(|section| section.to_str().to_string()).collect(),
+            skills: game_data.skill_names().into_iter().map(str::to_string).collect(),
+            talents: game_data.talent_names().into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    // Start of the completed text in the line and its candidates. Skills and talents are completed as a whole phrase,
+    // everything else word by word
+    fn candidates(&self, line: &str) -> (usize, Vec<String>) {
+        let Some((command, _)) = line.split_once(' ') else {
+            return (0, Self::starting_with(COMMANDS.iter().map(|(name, _)| *name), line, false));
+        };
+        let phrase_start = command.len() + 1;
+        let phrase = &line[phrase_start..];
+        let word_start = line.rfind(' ').map_or(0, |space| space + 1);
+        let word = &line[word_start..];
+
+        match command {
+            "gen" => (word_start, Self::starting_with(self.professions.iter().chain(self.species.iter()).map(String::as_str), word, false)),
+            "reroll" => (word_start, Self::starting_with(self.characteristics.iter().chain(self.sections.iter()).map(String::as_str), word, false)),
+            "lock" | "unlock" => (word_start, Self::starting_with(self.sections.iter().map(String::as_str), word, false)),
+            "add-talent" => (phrase_start, Self::starting_with(self.talents.iter().map(String::as_str), phrase, true)),
+            "add-skill" => (phrase_start, Self::starting_with(self.skills.iter().map(String::as_str), phrase, true)),
+            _ => (word_start, vec![]),
+        }
+    }
+
+    fn starting_with<'a>(candidates: impl Iterator<Item=&'a str>, prefix: &str, ignore_case: bool) -> Vec<String> {
+        candidates.filter(|candidate| match ignore_case {
+                      true => candidate.to_lowercase().starts_with(&prefix.to_lowercase()),
+                      false => candidate.starts_with(prefix),
+                  })
+                  .map(str::to_string)
+                  .collect()
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, candidates) = self.candidates(&line[..pos]);
+        Ok((start, candidates.into_iter().map(|candidate| Pair { display: candidate.clone(), replacement: candidate }).collect()))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_generate() {
+        assert_eq!(ReplCommand::parse("gen soldier_2 outlaw_1 dwarf"),
+                   Ok(Some(ReplCommand::Generate { careers: vec!["soldier_2".into(), "outlaw_1".into()], species: "dwarf".into() })));
+        assert_eq!(ReplCommand::parse("gen dwarf"), Err(CommandError::TooFewArguments(1, 2)));
+        assert_eq!(ReplCommand::parse("gen soldier dwarf"), Err(CommandError::InvalidArguments));
+        assert_eq!(ReplCommand::parse("   "), Ok(None));
+        assert_eq!(ReplCommand::parse("fly"), Err(CommandError::InvalidCommand));
+    }
+
+    #[test]
+    fn parse_reroll() {
+        assert_eq!(ReplCommand::parse("reroll"), Ok(Some(ReplCommand::Reroll(vec![]))));
+        assert_eq!(ReplCommand::parse("reroll strength"), Ok(Some(ReplCommand::RerollCharacteristic(BaseAttribute::Strength))));
+        assert_eq!(ReplCommand::parse("reroll weapon_skill"), Ok(Some(ReplCommand::RerollCharacteristic(BaseAttribute::WeaponSkill))));
+        assert_eq!(ReplCommand::parse("reroll Fel"), Ok(Some(ReplCommand::RerollCharacteristic(BaseAttribute::Fellowship))));
+        assert_eq!(ReplCommand::parse("reroll talents skills"), Ok(Some(ReplCommand::Reroll(vec![CharacterSection::Talents, CharacterSection::Skills]))));
+        assert_eq!(ReplCommand::parse("reroll wounds"), Err(CommandError::InvalidArguments));
+    }
+
+    #[test]
+    fn parse_lock() {
+        assert_eq!(ReplCommand::parse("lock characteristics name"), Ok(Some(ReplCommand::Lock(vec![CharacterSection::Characteristics, CharacterSection::Name]))));
+        assert_eq!(ReplCommand::parse("lock"), Err(CommandError::TooFewArguments(0, 1)));
+        assert_eq!(ReplCommand::parse("unlock"), Ok(Some(ReplCommand::Unlock(vec![]))));
+    }
+
+    #[test]
+    fn parse_talents_skills_and_save() {
+        assert_eq!(ReplCommand::parse("add-talent Very  Strong"), Ok(Some(ReplCommand::AddTalent("Very Strong".into()))));
+        assert_eq!(ReplCommand::parse("add-skill Melee (Basic)"), Ok(Some(ReplCommand::AddSkill("Melee (Basic)".into(), 5))));
+        assert_eq!(ReplCommand::parse("add-skill Dodge 10"), Ok(Some(ReplCommand::AddSkill("Dodge".into(), 10))));
+        assert_eq!(ReplCommand::parse("save grimnir"), Ok(Some(ReplCommand::Save("grimnir".into()))));
+        assert_eq!(ReplCommand::parse("save ../grimnir"), Err(CommandError::InvalidArguments));
+        assert_eq!(ReplCommand::parse("load grimnir"), Ok(Some(ReplCommand::Load("grimnir".into()))));
+    }
+
+    #[test]
+    fn completes_from_game_data() {
+        let helper = ReplHelper::new(&GameData::embedded());
+        assert_eq!(helper.candidates("add-t"), (0, vec!["add-talent".to_string()]));
+        assert_eq!(helper.candidates("gen soldier_2 dw"), (14, vec!["dwarf".to_string()]));
+        assert!(helper.candidates("gen sold").1.contains(&"soldier_3".to_string()));
+        assert!(helper.candidates("gen sold").1.contains(&"soldier_4".to_string()));
+        assert_eq!(helper.candidates("reroll stre"), (7, vec!["strength".to_string()]));
+        assert_eq!(helper.candidates("lock name t"), (10, vec!["talents".to_string(), "trappings".to_string()]));
+        assert_eq!(helper.candidates("add-talent very s"), (11, vec!["Very Strong".to_string()]));
+        assert!(helper.candidates("add-skill mel").1.contains(&"Melee".to_string()));
+        assert!(helper.candidates("show sold").1.is_empty());
+    }
+}
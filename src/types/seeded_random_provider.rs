@@ -0,0 +1,70 @@
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use crate::models::generation_base::GenerationBase;
+use crate::interfaces::random_provider::RandomProvider;
+
+// Gives the same rolls for the same seed, so a generated NPC can be reproduced
+pub struct SeededRandomProvider {
+    seed: u64,
+    rng: RefCell<StdRng>,
+}
+
+// Spreads consecutive streams over the whole seed space
+const STREAM_MULTIPLIER: u64 = 0x9E37_79B9_7F4A_7C15;
+
+impl SeededRandomProvider {
+    pub fn new(seed: u64) -> Self {
+        SeededRandomProvider { seed, rng: RefCell::new(StdRng::seed_from_u64(seed)) }
+    }
+}
+
+impl RandomProvider for SeededRandomProvider {
+    fn generate(&self, base: &GenerationBase) -> u32 {
+        let mut rng = self.rng.borrow_mut();
+        let mut result = base.base_value;
+        for dice in base.dices.iter() {
+            result += rng.gen_range(1..=*dice);
+        }
+        result
+    }
+
+    // Forks only depend on the seed, so the n-th NPC of a batch can be reproduced on its own with the forked seed
+    fn fork(&self, stream: u64) -> Self {
+        SeededRandomProvider::new(self.seed ^ stream.wrapping_add(1).wrapping_mul(STREAM_MULTIPLIER))
+    }
+
+    fn seed(&self) -> Option<u64> {
+        Some(self.seed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_rolls() {
+        let base = GenerationBase{base_value: 20, dices: vec![10, 10]};
+        let first = SeededRandomProvider::new(1513);
+        let second = SeededRandomProvider::new(1513);
+        let first_rolls: Vec<u32> = (0..20).map(|_| first.generate(&base)).collect();
+        let second_rolls: Vec<u32> = (0..20).map(|_| second.generate(&base)).collect();
+
+        assert_eq!(first_rolls, second_rolls);
+        assert!(first_rolls.iter().all(|roll| (22..=40).contains(roll)));
+    }
+
+    #[test]
+    fn forks_are_reproducible_and_independent() {
+        let base = GenerationBase{base_value: 0, dices: vec![100]};
+        let master = SeededRandomProvider::new(1513);
+        master.generate(&base);
+        let rolls = |provider: &SeededRandomProvider| (0..20).map(|_| provider.generate(&base)).collect::<Vec<u32>>();
+
+        assert_eq!(rolls(&master.fork(3)), rolls(&SeededRandomProvider::new(1513).fork(3)));
+        assert_eq!(rolls(&master.fork(3)), rolls(&SeededRandomProvider::new(master.fork(3).seed().unwrap())));
+        assert_ne!(rolls(&master.fork(3)), rolls(&master.fork(4)));
+        assert_ne!(master.fork(0).seed(), master.seed());
+    }
+}
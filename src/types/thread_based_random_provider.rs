@@ -14,6 +14,10 @@ impl RandomProvider for ThreadBasedRandomProvider {
         }
         result
     }
+
+    fn fork(&self, _stream: u64) -> Self {
+        ThreadBasedRandomProvider {}
+    }
 }
 
 #[cfg(test)]
@@ -35,7 +39,7 @@ mod test {
         let values = GenerationBase{base_value: 5, dices: vec![6]};
         let result = provider.generate(&values);
 
-        assert!(result >= 6 && result <= 11);
+        assert!((6..=11).contains(&result));
     }
 
     #[test]
@@ -44,6 +48,6 @@ mod test {
         let values = GenerationBase{base_value: 1, dices: vec![7,8,9]};
         let result = provider.generate(&values);
 
-        assert!(result >= 4 && result <= 25);
+        assert!((4..=25).contains(&result));
     }
 }
\ No newline at end of file